@@ -5,7 +5,16 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     Io(std::io::Error),
     Json(serde_json::Error),
-    IncorrectVersionFormat,
+    Zip(zip::result::ZipError),
+    InvalidVersion(crate::VersionError),
+    InvalidSnippet { name: String, error: crate::SyntaxError },
+    InvalidSnippetJson { name: String, error: String },
+    InvalidManifest(String),
+    InvalidTheme { name: String, error: String },
+    InvalidGrammar { name: String, error: String },
+    InvalidLanguage { id: String, error: String },
+    InvalidKeybinding { key: String, error: String },
+    InvalidSetting { key: String, error: String },
 }
 
 impl std::fmt::Display for Error {
@@ -14,7 +23,16 @@ impl std::fmt::Display for Error {
         match &self {
             Self::Io(e) => write!(f, "{e}"),
             Self::Json(e) => write!(f, "{e}"),
-            Self::IncorrectVersionFormat => write!(f, "Incorrect package version format"),
+            Self::Zip(e) => write!(f, "{e}"),
+            Self::InvalidVersion(e) => write!(f, "Invalid package version: {e}"),
+            Self::InvalidSnippet { name, error } => write!(f, "Invalid snippet '{name}' body: {error}"),
+            Self::InvalidSnippetJson { name, error } => write!(f, "Invalid snippet '{name}' JSON: {error}"),
+            Self::InvalidManifest(e) => write!(f, "Invalid package manifest: {e}"),
+            Self::InvalidTheme { name, error } => write!(f, "Invalid color theme '{name}': {error}"),
+            Self::InvalidGrammar { name, error } => write!(f, "Invalid grammar '{name}': {error}"),
+            Self::InvalidLanguage { id, error } => write!(f, "Invalid language '{id}': {error}"),
+            Self::InvalidKeybinding { key, error } => write!(f, "Invalid keybinding '{key}': {error}"),
+            Self::InvalidSetting { key, error } => write!(f, "Invalid setting '{key}': {error}"),
         }
     }
 }
@@ -32,3 +50,17 @@ impl From<serde_json::Error> for Error {
         Self::Json(e)
     }
 }
+
+impl From<zip::result::ZipError> for Error {
+    /// Creates the Error from 'zip::result::ZipError'
+    fn from(e: zip::result::ZipError) -> Self {
+        Self::Zip(e)
+    }
+}
+
+impl From<crate::VersionError> for Error {
+    /// Creates the Error from 'VersionError'
+    fn from(e: crate::VersionError) -> Self {
+        Self::InvalidVersion(e)
+    }
+}
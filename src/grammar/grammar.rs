@@ -0,0 +1,365 @@
+use crate::prelude::*;
+use super::Rule;
+use serde::{ Serialize, Deserialize };
+use std::path::{ Path, PathBuf };
+
+/// The TextMate grammar
+/// * name - the grammar name (example: "MyDSL")
+/// * scope_name - the root scope name (example: "source.mydsl")
+/// * file_types - the file extensions of grammar (example: ["mydsl"])
+/// * first_line_match - the regex of the first line of grammar files (example: r"^#!.*\bmydsl\b")
+/// * patterns - the root rules
+/// * repository - the named rules in insertion order, where <(KEY, RULE)> (referenced as "#KEY")
+/// * injection_selector - the scope selector where the grammar is injected (example: "L:source.js")
+/// * injections - the rules injected into scopes of this grammar, where <(SELECTOR, RULE)>
+/// * extra - the other grammar fields which are kept as is
+/// * language - the language id (the injection grammars have no language)
+/// * file_name - the grammar file name
+/// * embedded_languages - the embedded languages scopes, where <(SCOPE_NAME, LANGUAGE_ID)>
+/// * inject_to - the root scope names of grammars where the injection grammar is applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Grammar {
+    #[serde(rename = "$schema", default = "Grammar::schema")]
+    schema: String,
+    pub name: String,
+    #[serde(rename = "scopeName")]
+    pub scope_name: String,
+    #[serde(rename = "fileTypes", skip_serializing_if = "Vec::is_empty", default)]
+    pub file_types: Vec<String>,
+    #[serde(rename = "firstLineMatch", skip_serializing_if = "Option::is_none", default)]
+    pub first_line_match: Option<String>,
+    #[serde(default)]
+    pub patterns: Vec<Rule>,
+    #[serde(skip_serializing_if = "OrderedMap::is_empty", default)]
+    repository: OrderedMap<String, Rule>,
+    #[serde(rename = "injectionSelector", skip_serializing_if = "Option::is_none", default)]
+    pub injection_selector: Option<String>,
+    #[serde(skip_serializing_if = "OrderedMap::is_empty", default)]
+    injections: OrderedMap<String, Rule>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+
+    #[serde(skip)]
+    pub language: Option<String>,
+    #[serde(skip)]
+    pub file_name: PathBuf,
+    #[serde(skip)]
+    embedded_languages: Vec<(String, String)>,
+    #[serde(skip)]
+    inject_to: Vec<String>,
+}
+
+impl Grammar {
+    /// Creates a new language grammar
+    /// * language - the language id (example: "mydsl")
+    /// * name - the grammar name (example: "MyDSL")
+    /// * scope_name - the root scope name (example: "source.mydsl")
+    pub fn new<S>(language: S, name: S, scope_name: S) -> Self
+    where S: Into<String>
+    {
+        Self::base(Some(language.into()), name.into(), scope_name.into(), None)
+    }
+
+    /// Creates a new injection grammar (the grammar rules are injected into other grammars)
+    /// * name - the grammar name (example: "MyDSL in JS comments")
+    /// * scope_name - the root scope name (example: "mydsl.injection")
+    /// * selector - the scope selector where the grammar is injected (example: "L:comment.block.js")
+    pub fn injection<S>(name: S, scope_name: S, selector: S) -> Self
+    where S: Into<String>
+    {
+        Self::base(None, name.into(), scope_name.into(), Some(selector.into()))
+    }
+
+    /// Creates a new grammar without rules
+    fn base(language: Option<String>, name: String, scope_name: String, injection_selector: Option<String>) -> Self {
+        Self {
+            schema: Self::schema(),
+            file_name: Self::default_file_name(&name),
+            name,
+            scope_name,
+            file_types: vec![],
+            first_line_match: None,
+            patterns: vec![],
+            repository: OrderedMap::default(),
+            injection_selector,
+            injections: OrderedMap::default(),
+            extra: serde_json::Map::new(),
+            language,
+            embedded_languages: vec![],
+            inject_to: vec![],
+        }
+    }
+
+    /// The grammar JSON schema URL
+    fn schema() -> String {
+        "https://raw.githubusercontent.com/martinring/tmlanguage/master/tmlanguage.json".into()
+    }
+
+    /// Generates the grammar file name from its name (example: "mydsl.tmLanguage.json")
+    fn default_file_name(name: &str) -> PathBuf {
+        format!("{}.tmLanguage.json", to_latin_text(&name.to_lowercase(), true)).into()
+    }
+
+    /// Sets the grammar file name && returns 'Self'
+    /// * file_name - the grammar file name (example: "mydsl.tmLanguage.json")
+    pub fn set_file_name<P>(mut self, file_name: P) -> Self
+    where P: Into<PathBuf> {
+        self.file_name = file_name.into();
+        self
+    }
+
+    /// Adds the file extension of grammar && returns 'Self'
+    /// * file_type - the file extension without dot (example: "mydsl")
+    pub fn add_file_type<S>(mut self, file_type: S) -> Self
+    where S: Into<String> {
+        self.file_types.push(file_type.into());
+        self
+    }
+
+    /// Sets the regex of the first line of grammar files && returns 'Self'
+    /// * regex - the first line regex (example: r"^#!.*\bmydsl\b")
+    pub fn set_first_line_match<S>(mut self, regex: S) -> Self
+    where S: Into<String> {
+        self.first_line_match = Some(regex.into());
+        self
+    }
+
+    /// Adds the root rule && returns 'Self'
+    pub fn add_pattern(mut self, rule: Rule) -> Self {
+        self.patterns.push(rule);
+        self
+    }
+
+    /// Adds the named rule to repository && returns 'Self' (the existing rule is replaced)
+    /// * key - the rule key (referenced as "#KEY")
+    /// * rule - the grammar rule
+    pub fn add_rule<S>(mut self, key: S, rule: Rule) -> Self
+    where S: Into<String>
+    {
+        let key = key.into();
+        match self.repository.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, value)) => *value = rule,
+            None => self.repository.0.push((key, rule)),
+        }
+        self
+    }
+
+    /// Adds the rule injected into scopes of this grammar && returns 'Self'
+    /// * selector - the scope selector (example: "L:string.quoted.mydsl")
+    /// * rule - the grammar rule
+    pub fn add_injection<S>(mut self, selector: S, rule: Rule) -> Self
+    where S: Into<String>
+    {
+        self.injections.0.push((selector.into(), rule));
+        self
+    }
+
+    /// Adds the embedded language && returns 'Self'
+    /// * scope_name - the embedded scope name (example: "meta.embedded.block.sql")
+    /// * language - the embedded language id (example: "sql")
+    pub fn embed_language<S>(mut self, scope_name: S, language: S) -> Self
+    where S: Into<String>
+    {
+        self.embedded_languages.push((scope_name.into(), language.into()));
+        self
+    }
+
+    /// Adds the grammar where the injection grammar is applied && returns 'Self'
+    /// * scope_name - the root scope name of grammar (example: "source.js")
+    pub fn inject_to<S>(mut self, scope_name: S) -> Self
+    where S: Into<String> {
+        self.inject_to.push(scope_name.into());
+        self
+    }
+
+    /// Get the named rule by its key
+    pub fn rule(&self, key: &str) -> Option<&Rule> {
+        self.repository.0.iter().find(|(k, _)| k == key).map(|(_, r)| r)
+    }
+
+    /// Get the named rules in insertion order, where <(KEY, RULE)>
+    pub fn repository(&self) -> &[(String, Rule)] {
+        &self.repository.0
+    }
+
+    /// Get the injected rules in insertion order, where <(SELECTOR, RULE)>
+    pub fn injections(&self) -> &[(String, Rule)] {
+        &self.injections.0
+    }
+
+    /// Get the embedded languages, where <(SCOPE_NAME, LANGUAGE_ID)>
+    pub fn embedded_languages(&self) -> &[(String, String)] {
+        &self.embedded_languages
+    }
+
+    /// Get the root scope names of grammars where the injection grammar is applied
+    pub fn injected_to(&self) -> &[String] {
+        &self.inject_to
+    }
+
+    /// Checks the grammar: the rules structure, the 'include' references && the regexes syntax
+    /// (the Oniguruma regexes are checked approximately by 'fancy_regex')
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |error: String| Error::InvalidGrammar { name: self.scope_name.clone(), error };
+
+        if self.scope_name.is_empty() || self.scope_name.contains(char::is_whitespace) {
+            return Err(invalid(format!("the scope name '{}' is invalid", self.scope_name)));
+        }
+
+        if let Some(regex) = &self.first_line_match {
+            check_regex(regex, false).map_err(|e| invalid(format!("the firstLineMatch regex '{regex}' is invalid: {e}")))?;
+        }
+
+        let repos = [self.repository()];
+        for (i, rule) in self.patterns.iter().enumerate() {
+            check_rule(rule, &format!("patterns[{i}]"), &repos).map_err(invalid)?;
+        }
+        for (key, rule) in &self.repository.0 {
+            check_rule(rule, &format!("repository.{key}"), &repos).map_err(invalid)?;
+        }
+        for (selector, rule) in &self.injections.0 {
+            check_rule(rule, &format!("injections[{selector}]"), &repos).map_err(invalid)?;
+        }
+
+        Ok(())
+    }
+
+    /// Converts the grammar to JSON string
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self).map_err(Error::from)
+    }
+
+    /// Imports the grammar from TextMate grammar JSON (the comments && trailing commas are allowed)
+    /// * language - the language id (None for injection grammar)
+    /// * json - the grammar file contents
+    pub fn from_json(language: Option<&str>, json: &str) -> Result<Self> {
+        let mut this: Self = serde_json::from_str(&strip_json_comments(json))?;
+        this.language = language.map(String::from);
+        this.file_name = Self::default_file_name(&this.name);
+        Ok(this)
+    }
+
+    /// Writes the grammar to file "%DIR/syntaxes/%FILE_NAME"
+    /// * dir - the package root directory path (without '/syntaxes' folder)
+    pub fn write_to<P>(&self, dir: P) -> Result<()>
+    where P: Into<PathBuf>
+    {
+        self.write_into(&mut crate::FsSink::new(dir))
+    }
+
+    /// Writes the grammar file "syntaxes/<FILE_NAME>" to output
+    /// * sink - the package output
+    pub fn write_into(&self, sink: &mut dyn crate::OutputSink) -> Result<()> {
+        let path = Path::new("syntaxes").join(&self.file_name);
+        sink.write_file(&path, self.to_json()?.as_bytes())
+    }
+}
+
+/// Checks the grammar rule && its nested rules
+/// * rule - the grammar rule
+/// * place - the rule path in grammar (example: "repository.strings.patterns[0]")
+/// * repos - the repositories of grammar && enclosing rules (from outermost to innermost)
+fn check_rule(rule: &Rule, place: &str, repos: &[&[(String, Rule)]]) -> std::result::Result<(), String> {
+    // checking the rule kind:
+    let is_block = rule.begin.is_some();
+    match () {
+        _ if rule.include.is_some() && (rule.match_.is_some() || is_block) => return Err(format!("{place}: the 'include' rule can't have 'match' or 'begin'")),
+        _ if rule.match_.is_some() && is_block => return Err(format!("{place}: the rule can't have both 'match' && 'begin'")),
+        _ if is_block && rule.end.is_some() == rule.while_.is_some() => return Err(format!("{place}: the 'begin' rule must have either 'end' or 'while'")),
+        _ if !is_block && (rule.end.is_some() || rule.while_.is_some()) => return Err(format!("{place}: the 'end' && 'while' require 'begin'")),
+        _ => {}
+    }
+
+    // the rule repository is visible in the rule && its nested rules:
+    let mut repos = repos.to_vec();
+    if !rule.repository().is_empty() {
+        repos.push(rule.repository());
+    }
+
+    // checking the reference (the key is searched from the innermost repository):
+    if let Some(include) = &rule.include {
+        let found = |key: &str| repos.iter().any(|repo| repo.iter().any(|(k, _)| k == key));
+        match include.strip_prefix('#') {
+            Some(key) if !found(key) => return Err(format!("{place}: the include '{include}' isn't found in repository")),
+            _ if include.is_empty() => return Err(format!("{place}: the include is empty")),
+            _ => {}
+        }
+    }
+
+    // checking the regexes:
+    for (key, regex, is_end) in [("match", &rule.match_, false), ("begin", &rule.begin, false), ("end", &rule.end, true), ("while", &rule.while_, true)] {
+        if let Some(regex) = regex {
+            check_regex(regex, is_end).map_err(|e| format!("{place}: the {key} regex '{regex}' is invalid: {e}"))?;
+        }
+    }
+
+    // checking the captures && nested rules:
+    for (key, captures) in rule.captures() {
+        for (index, capture) in captures {
+            if index.parse::<usize>().is_err() {
+                return Err(format!("{place}: the {key} group '{index}' isn't a number"));
+            }
+            for (i, nested) in capture.patterns.iter().enumerate() {
+                check_rule(nested, &format!("{place}.{key}.{index}.patterns[{i}]"), &repos)?;
+            }
+        }
+    }
+    for (i, nested) in rule.patterns.iter().enumerate() {
+        check_rule(nested, &format!("{place}.patterns[{i}]"), &repos)?;
+    }
+    for (key, nested) in rule.repository() {
+        check_rule(nested, &format!("{place}.repository.{key}"), &repos)?;
+    }
+
+    Ok(())
+}
+
+/// Compiles the Oniguruma regex by 'fancy_regex' (the 'end' && 'while' back-references to 'begin' groups are allowed),
+/// the check is approximate: the Oniguruma-only escapes '\h', '\H' && '\Z' are translated before compiling,
+/// but some Oniguruma syntax could be still rejected or accepted with different meaning
+/// * regex - the grammar regex
+/// * is_end - the regex is 'end' or 'while' match
+fn check_regex(regex: &str, is_end: bool) -> std::result::Result<(), String> {
+    let mut pattern = String::with_capacity(regex.len());
+    let mut chars = regex.chars().peekable();
+    let mut class_depth = 0;
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(d)) if is_end && d.is_ascii_digit() => {
+                while chars.next_if(|d| d.is_ascii_digit()).is_some() {}
+                pattern.push_str("(?:)");
+            }
+            // the hex digit (the '\h' is a horizontal space in 'fancy_regex'):
+            ('\\', Some('h')) => {
+                chars.next();
+                pattern.push_str(r"\p{ASCII_Hex_Digit}");
+            }
+            ('\\', Some('H')) => {
+                chars.next();
+                pattern.push_str(r"\P{ASCII_Hex_Digit}");
+            }
+            // the end of text or the position before the final line break:
+            ('\\', Some('Z')) if class_depth == 0 => {
+                chars.next();
+                pattern.push_str(r"(?=\n?\z)");
+            }
+            ('\\', Some(&next)) => {
+                chars.next();
+                pattern.push(c);
+                pattern.push(next);
+            }
+            ('[', _) => {
+                class_depth += 1;
+                pattern.push(c);
+            }
+            (']', _) if class_depth > 0 => {
+                class_depth -= 1;
+                pattern.push(c);
+            }
+            _ => pattern.push(c),
+        }
+    }
+
+    fancy_regex::Regex::new(&pattern).map(|_| ()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,3 @@
+pub mod rule;       pub use rule::*;
+#[allow(clippy::module_inception)]
+pub mod grammar;    pub use grammar::*;
@@ -0,0 +1,263 @@
+use crate::prelude::*;
+use serde::{ Serialize, Deserialize };
+
+/// The TextMate grammar rule
+/// * name - the scope name of matched text (example: "keyword.control.mydsl")
+/// * content_name - the scope name of text between begin && end matches
+/// * include - the reference to other rule: "#REPOSITORY_KEY", "$self", "$base" or "SCOPE_NAME[#KEY]"
+/// * match_ - the regex of single line match
+/// * begin - the regex of block start
+/// * end - the regex of block end (the begin captures are available as "\1", "\2", etc.)
+/// * while_ - the regex which must match each next line to continue block
+/// * captures - the scopes of 'match' (or both 'begin' && 'end') groups, where <(GROUP_INDEX, CAPTURE)>
+/// * begin_captures, end_captures, while_captures - the scopes of 'begin', 'end' && 'while' groups
+/// * apply_end_pattern_last - the 'end' regex is tried after the nested rules
+/// * patterns - the nested rules
+/// * repository - the named rules visible in this rule only, where <(KEY, RULE)> (referenced as "#KEY")
+/// * comment - the rule comment
+/// * extra - the other rule fields which are kept as is
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name: Option<String>,
+    #[serde(rename = "contentName", skip_serializing_if = "Option::is_none", default)]
+    pub content_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub include: Option<String>,
+    #[serde(rename = "match", skip_serializing_if = "Option::is_none", default)]
+    pub match_: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub begin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub end: Option<String>,
+    #[serde(rename = "while", skip_serializing_if = "Option::is_none", default)]
+    pub while_: Option<String>,
+    #[serde(skip_serializing_if = "OrderedMap::is_empty", default)]
+    captures: OrderedMap<String, Capture>,
+    #[serde(rename = "beginCaptures", skip_serializing_if = "OrderedMap::is_empty", default)]
+    begin_captures: OrderedMap<String, Capture>,
+    #[serde(rename = "endCaptures", skip_serializing_if = "OrderedMap::is_empty", default)]
+    end_captures: OrderedMap<String, Capture>,
+    #[serde(rename = "whileCaptures", skip_serializing_if = "OrderedMap::is_empty", default)]
+    while_captures: OrderedMap<String, Capture>,
+    #[serde(rename = "applyEndPatternLast", skip_serializing_if = "Option::is_none", deserialize_with = "de_flag", default)]
+    pub apply_end_pattern_last: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub patterns: Vec<Rule>,
+    #[serde(skip_serializing_if = "OrderedMap::is_empty", default)]
+    repository: OrderedMap<String, Rule>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Rule {
+    /// Creates the single line match rule
+    /// * regex - the match regex (example: r"\b(if|else)\b")
+    /// * name - the scope name (example: "keyword.control.mydsl")
+    pub fn matches<S>(regex: S, name: S) -> Self
+    where S: Into<String>
+    {
+        Self { match_: Some(regex.into()), name: Some(name.into()), ..Self::default() }
+    }
+
+    /// Creates the block rule from begin to end match
+    /// * begin - the block start regex (example: "\"")
+    /// * end - the block end regex (example: "\"")
+    /// * name - the scope name (example: "string.quoted.double.mydsl")
+    pub fn block<S>(begin: S, end: S, name: S) -> Self
+    where S: Into<String>
+    {
+        Self { begin: Some(begin.into()), end: Some(end.into()), name: Some(name.into()), ..Self::default() }
+    }
+
+    /// Creates the block rule which continues while each next line matches
+    /// * begin - the block start regex (example: "^>")
+    /// * while_ - the block continuation regex (example: "^>")
+    /// * name - the scope name (example: "markup.quote.mydsl")
+    pub fn while_block<S>(begin: S, while_: S, name: S) -> Self
+    where S: Into<String>
+    {
+        Self { begin: Some(begin.into()), while_: Some(while_.into()), name: Some(name.into()), ..Self::default() }
+    }
+
+    /// Creates the reference to other rule
+    /// * reference - the rule reference: "#REPOSITORY_KEY", "$self", "$base" or "SCOPE_NAME[#KEY]"
+    pub fn include<S>(reference: S) -> Self
+    where S: Into<String>
+    {
+        Self { include: Some(reference.into()), ..Self::default() }
+    }
+
+    /// Creates the rules group (the rule with nested patterns only)
+    /// * patterns - the nested rules
+    pub fn group(patterns: Vec<Rule>) -> Self {
+        Self { patterns, ..Self::default() }
+    }
+
+    /// Sets the scope name && returns 'Self'
+    pub fn set_name<S>(mut self, name: S) -> Self
+    where S: Into<String> {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the scope name of block contents && returns 'Self'
+    pub fn set_content_name<S>(mut self, name: S) -> Self
+    where S: Into<String> {
+        self.content_name = Some(name.into());
+        self
+    }
+
+    /// Adds the nested rule && returns 'Self'
+    pub fn add_pattern(mut self, rule: Rule) -> Self {
+        self.patterns.push(rule);
+        self
+    }
+
+    /// Adds the named rule to the rule repository && returns 'Self' (the existing rule is replaced)
+    /// * key - the rule key (referenced as "#KEY" from this rule && its nested rules)
+    /// * rule - the grammar rule
+    pub fn add_rule<S>(mut self, key: S, rule: Rule) -> Self
+    where S: Into<String>
+    {
+        let key = key.into();
+        match self.repository.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, value)) => *value = rule,
+            None => self.repository.0.push((key, rule)),
+        }
+        self
+    }
+
+    /// Sets the 'end' regex to be tried after the nested rules && returns 'Self'
+    pub fn set_apply_end_pattern_last(mut self, last: bool) -> Self {
+        self.apply_end_pattern_last = Some(last);
+        self
+    }
+
+    /// Sets the rule comment && returns 'Self'
+    pub fn set_comment<S>(mut self, comment: S) -> Self
+    where S: Into<String> {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Get the named rule of the rule repository by its key
+    pub fn rule(&self, key: &str) -> Option<&Rule> {
+        self.repository.0.iter().find(|(k, _)| k == key).map(|(_, r)| r)
+    }
+
+    /// Get the named rules of the rule repository in insertion order, where <(KEY, RULE)>
+    pub fn repository(&self) -> &[(String, Rule)] {
+        &self.repository.0
+    }
+
+    /// Sets the scope of 'match' (or both 'begin' && 'end') group && returns 'Self'
+    /// * index - the regex group index (0 is the whole match)
+    /// * capture - the capture scope name (or [Capture] with nested rules)
+    pub fn capture<C>(mut self, index: usize, capture: C) -> Self
+    where C: Into<Capture> {
+        set_capture(&mut self.captures, index, capture.into());
+        self
+    }
+
+    /// Sets the scope of 'begin' group && returns 'Self'
+    pub fn begin_capture<C>(mut self, index: usize, capture: C) -> Self
+    where C: Into<Capture> {
+        set_capture(&mut self.begin_captures, index, capture.into());
+        self
+    }
+
+    /// Sets the scope of 'end' group && returns 'Self'
+    pub fn end_capture<C>(mut self, index: usize, capture: C) -> Self
+    where C: Into<Capture> {
+        set_capture(&mut self.end_captures, index, capture.into());
+        self
+    }
+
+    /// Sets the scope of 'while' group && returns 'Self'
+    pub fn while_capture<C>(mut self, index: usize, capture: C) -> Self
+    where C: Into<Capture> {
+        set_capture(&mut self.while_captures, index, capture.into());
+        self
+    }
+
+    /// Get the all captures lists, where <(JSON_KEY, <(GROUP_INDEX, CAPTURE)>)>
+    pub fn captures(&self) -> [(&'static str, &[(String, Capture)]); 4] {
+        [
+            ("captures", &self.captures.0),
+            ("beginCaptures", &self.begin_captures.0),
+            ("endCaptures", &self.end_captures.0),
+            ("whileCaptures", &self.while_captures.0),
+        ]
+    }
+}
+
+/// Reads the flag from boolean or number (the TextMate grammars use both: `true` or `1`)
+fn de_flag<'de, D>(deserializer: D) -> std::result::Result<Option<bool>, D::Error>
+where D: serde::Deserializer<'de>
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Bool(bool),
+        Number(i64),
+    }
+
+    Ok(Some(match Repr::deserialize(deserializer)? {
+        Repr::Bool(flag) => flag,
+        Repr::Number(n) => n != 0,
+    }))
+}
+
+/// Sets the capture by group index (the existing capture is replaced)
+fn set_capture(captures: &mut OrderedMap<String, Capture>, index: usize, capture: Capture) {
+    let key = index.to_string();
+    match captures.0.iter_mut().find(|(k, _)| *k == key) {
+        Some((_, value)) => *value = capture,
+        None => captures.0.push((key, capture)),
+    }
+}
+
+
+/// The regex group capture
+/// * name - the scope name of captured text
+/// * patterns - the rules applied to captured text
+/// * extra - the other capture fields which are kept as is
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Capture {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub patterns: Vec<Rule>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Capture {
+    /// Creates the capture with scope name
+    /// * name - the scope name (example: "entity.name.function.mydsl")
+    pub fn new<S>(name: S) -> Self
+    where S: Into<String> {
+        Self { name: Some(name.into()), ..Self::default() }
+    }
+
+    /// Adds the rule applied to captured text && returns 'Self'
+    pub fn add_pattern(mut self, rule: Rule) -> Self {
+        self.patterns.push(rule);
+        self
+    }
+}
+
+impl From<&str> for Capture {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Capture {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
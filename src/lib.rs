@@ -39,8 +39,8 @@
 //!                         "Display",
 //!                         "Clone",
 //!                         "Copy",
-//!                         "Eq\\, PartialEq",
-//!                         "Serialize\\, Deserialize",
+//!                         "Eq, PartialEq",
+//!                         "Serialize, Deserialize",
 //!                     ])),
 //!
 //!                     Snippet::attribute("attr-allow", "allow", Some(vec![
@@ -167,7 +167,8 @@
 //!             ),
 //!         ],
 //!         License::mit("DrakeN-inc"),
-//!     );
+//!     )
+//!     .set_publisher("DrakeN-inc");
 //!
 //!     // writing package files to directory "package/":
 //!     pkg.write_to("package")?;
@@ -194,4 +195,7 @@ pub mod tools;
 pub mod prelude;
 
 pub mod snippets;   pub use snippets::*;
+pub mod theme;      pub use theme::*;
+pub mod grammar;    pub use grammar::*;
 pub mod package;    pub use package::*;
+pub mod output;     pub use output::*;
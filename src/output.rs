@@ -0,0 +1,136 @@
+use crate::prelude::*;
+use std::{ collections::BTreeMap, path::{ Path, PathBuf }, fs };
+
+/// The output files sink (the destination of generated package files)
+pub trait OutputSink {
+    /// Writes the file
+    /// * path - the file path relative to the output root (example: "snippets/rust.code-snippets")
+    /// * data - the file contents
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> Result<()>;
+}
+
+
+/// The file system output (writes the files to directory, the missing directories are created)
+/// * root - the output root directory
+#[derive(Debug, Clone)]
+pub struct FsSink {
+    pub root: PathBuf,
+}
+
+impl FsSink {
+    /// Creates a new file system output
+    /// * root - the output root directory
+    pub fn new<P>(root: P) -> Self
+    where P: Into<PathBuf> {
+        Self { root: root.into() }
+    }
+}
+
+impl OutputSink for FsSink {
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> Result<()> {
+        let path = self.root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, data).map_err(Error::from)
+    }
+}
+
+
+/// The in-memory output (collects the files to sorted tree)
+/// * files - the written files, where <(PATH, CONTENTS)>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemorySink {
+    pub files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemorySink {
+    /// Creates a new empty in-memory output
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the file contents as text (returns 'None' if the file isn't written or isn't UTF-8)
+    /// * path - the file path relative to the output root
+    pub fn text<P>(&self, path: P) -> Option<&str>
+    where P: AsRef<Path> {
+        self.files.get(path.as_ref()).and_then(|data| std::str::from_utf8(data).ok())
+    }
+}
+
+impl OutputSink for MemorySink {
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> Result<()> {
+        self.files.insert(path.to_path_buf(), data.to_vec());
+        Ok(())
+    }
+}
+
+impl From<MemorySink> for BTreeMap<PathBuf, Vec<u8>> {
+    fn from(sink: MemorySink) -> Self {
+        sink.files
+    }
+}
+
+
+/// The planned file write action
+/// * Create - the file doesn't exist
+/// * Overwrite - the file exists with other contents
+/// * Unchanged - the file exists with the same contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteAction {
+    Create,
+    Overwrite,
+    Unchanged,
+}
+
+/// The planned file write
+/// * path - the file path relative to the output root
+/// * size - the file size in bytes
+/// * action - what happens with the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedWrite {
+    pub path: PathBuf,
+    pub size: usize,
+    pub action: WriteAction,
+}
+
+impl std::fmt::Display for PlannedWrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let action = match self.action {
+            WriteAction::Create => "create",
+            WriteAction::Overwrite => "overwrite",
+            WriteAction::Unchanged => "unchanged",
+        };
+        write!(f, "{action:<9} {} ({} bytes)", self.path.display(), self.size)
+    }
+}
+
+/// The dry-run output (lists what would be written to directory, nothing is written)
+/// * root - the output root directory (the existing files are compared with new contents)
+/// * planned - the planned writes
+#[derive(Debug, Clone)]
+pub struct DryRunSink {
+    pub root: PathBuf,
+    pub planned: Vec<PlannedWrite>,
+}
+
+impl DryRunSink {
+    /// Creates a new dry-run output
+    /// * root - the output root directory
+    pub fn new<P>(root: P) -> Self
+    where P: Into<PathBuf> {
+        Self { root: root.into(), planned: vec![] }
+    }
+}
+
+impl OutputSink for DryRunSink {
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> Result<()> {
+        let action = match fs::read(self.root.join(path)) {
+            Ok(old) if old == data => WriteAction::Unchanged,
+            Ok(_) => WriteAction::Overwrite,
+            Err(_) => WriteAction::Create,
+        };
+        self.planned.push(PlannedWrite { path: path.to_path_buf(), size: data.len(), action });
+        Ok(())
+    }
+}
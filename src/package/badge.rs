@@ -0,0 +1,27 @@
+use serde::{ Serialize, Deserialize };
+
+/// The marketplace page badge
+/// * url - the badge image URL (only the approved badge services are allowed)
+/// * href - the badge link
+/// * description - the badge description
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Badge {
+    pub url: String,
+    pub href: String,
+    pub description: String,
+}
+
+impl Badge {
+    /// Creates a new marketplace page badge
+    /// * url - the badge image URL
+    /// * href - the badge link
+    /// * description - the badge description
+    pub fn new<S>(url: S, href: S, description: S) -> Self
+    where S: Into<String> {
+        Self {
+            url: url.into(),
+            href: href.into(),
+            description: description.into(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{ Serialize, Deserialize };
+
+/// The package issue tracker info
+/// * url - the issue tracker page
+/// * email - the email for issues reporting
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bugs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl Bugs {
+    /// Creates the issue tracker info with email
+    /// * email - the email for issues reporting
+    pub fn email<S>(email: S) -> Self
+    where S: Into<String> {
+        Self {
+            url: None,
+            email: Some(email.into()),
+        }
+    }
+}
+
+impl From<&str> for Bugs {
+    fn from(s: &str) -> Self {
+        Self {
+            url: Some(s.to_owned()),
+            email: None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Bugs {
+    /// Reads the issue tracker from URL string or '{ "url": ..., "email": ... }' object
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Url(String),
+            Full { url: Option<String>, email: Option<String> },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Url(url) => Self::from(url.as_str()),
+            Repr::Full { url, email } => Self { url, email },
+        })
+    }
+}
@@ -1,7 +1,67 @@
-use serde::Serialize;
+use serde::{ Serialize, Deserialize };
 
-/// The package category
-#[derive(Debug, Clone, Copy, Serialize)]
+/// The package marketplace category (the unknown categories are read as 'Other')
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Category {
+    #[serde(rename = "Programming Languages")]
+    ProgrammingLanguages,
+    Themes,
+    Formatters,
+    Linters,
+    Debuggers,
+    Keymaps,
     Snippets,
+    #[serde(rename = "Language Packs")]
+    LanguagePacks,
+    #[serde(rename = "Data Science")]
+    DataScience,
+    Notebooks,
+    Education,
+    Testing,
+    #[serde(rename = "SCM Providers")]
+    ScmProviders,
+    Visualization,
+    Azure,
+    #[serde(rename = "Machine Learning")]
+    MachineLearning,
+    #[serde(rename = "Extension Packs")]
+    ExtensionPacks,
+    AI,
+    Chat,
+    #[serde(other)]
+    Other,
+}
+
+impl Category {
+    /// Get the category name as marketplace expects (example: "Programming Languages")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ProgrammingLanguages => "Programming Languages",
+            Self::Themes => "Themes",
+            Self::Formatters => "Formatters",
+            Self::Linters => "Linters",
+            Self::Debuggers => "Debuggers",
+            Self::Keymaps => "Keymaps",
+            Self::Snippets => "Snippets",
+            Self::LanguagePacks => "Language Packs",
+            Self::DataScience => "Data Science",
+            Self::Notebooks => "Notebooks",
+            Self::Education => "Education",
+            Self::Testing => "Testing",
+            Self::ScmProviders => "SCM Providers",
+            Self::Visualization => "Visualization",
+            Self::Azure => "Azure",
+            Self::MachineLearning => "Machine Learning",
+            Self::ExtensionPacks => "Extension Packs",
+            Self::AI => "AI",
+            Self::Chat => "Chat",
+            Self::Other => "Other",
+        }
+    }
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
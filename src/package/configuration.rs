@@ -0,0 +1,168 @@
+use crate::prelude::*;
+use super::Setting;
+use serde::{ Serialize, Deserialize };
+
+/// The settings model which is derived from Rust struct (the struct fields become the settings)
+///
+/// # Examples:
+/// ```
+/// use vscode_generator::{ prelude::*, Configuration, SettingsModel, SettingScope };
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Settings {
+///     enable: bool,
+///     max_items: u32,
+///     mode: String,
+/// }
+///
+/// impl Default for Settings {
+///     fn default() -> Self {
+///         Self { enable: true, max_items: 100, mode: "auto".into() }
+///     }
+/// }
+///
+/// impl SettingsModel for Settings {
+///     const PREFIX: &'static str = "myExt";
+///
+///     fn describe(config: Configuration) -> Configuration {
+///         config
+///             .update("enable", |s| s.set_descr("Enables the extension"))
+///             .update("mode", |s| s.add_enum("auto", "Detect automatically").add_enum("manual", "Ask every time"))
+///             .update("maxItems", |s| s.set_scope(SettingScope::Resource))
+///     }
+/// }
+///
+/// fn main() -> Result<()> {
+///     let config = Configuration::derive::<Settings>("My extension")?;
+///     assert_eq!(config.get("myExt.maxItems").unwrap().default, Some(100.into()));
+///     Ok(())
+/// }
+/// ```
+pub trait SettingsModel: Serialize + Default {
+    /// The settings keys prefix (example: "myExt")
+    const PREFIX: &'static str;
+
+    /// Refines the derived settings (the descriptions, enums, scopes, etc.)
+    /// * config - the configuration with settings derived from default value
+    fn describe(config: Configuration) -> Configuration {
+        config
+    }
+}
+
+
+/// The extension settings section (the 'contributes.configuration' entry)
+/// * title - the section title in the settings editor (the extension name is shown if empty)
+/// * order - the section position in the settings editor
+/// * properties - the settings in insertion order, where <(KEY, SETTING)>
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Configuration {
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub order: Option<i64>,
+    #[serde(default)]
+    properties: OrderedMap<String, Setting>,
+}
+
+impl Configuration {
+    /// Creates an empty settings section
+    /// * title - the section title
+    pub fn new<S>(title: S) -> Self
+    where S: Into<String> {
+        Self {
+            title: title.into(),
+            order: None,
+            properties: OrderedMap::default(),
+        }
+    }
+
+    /// Derives the settings section from the settings model (the keys are "%PREFIX.%FIELD")
+    /// * title - the section title
+    pub fn derive<T>(title: &str) -> Result<Self>
+    where T: SettingsModel {
+        Self::from_defaults(title, T::PREFIX, &T::default()).map(T::describe)
+    }
+
+    /// Creates the settings section from the default values (the serialized struct fields become the settings)
+    /// * title - the section title
+    /// * prefix - the settings keys prefix (example: "myExt")
+    /// * defaults - the default values (the struct which is serialized to JSON object)
+    pub fn from_defaults<T>(title: &str, prefix: &str, defaults: &T) -> Result<Self>
+    where T: Serialize {
+        // the round trip through the string keeps the fields order:
+        let fields = serde_json::from_str::<OrderedMap<String, serde_json::Value>>(&serde_json::to_string(defaults)?)
+            .map_err(|_| Error::InvalidSetting { key: prefix.into(), error: "the defaults aren't serialized to JSON object".into() })?;
+
+        let mut this = Self::new(title);
+        for (field, value) in fields.0 {
+            this = this.add_setting(format!("{prefix}.{field}"), Setting::with_default(value));
+        }
+        Ok(this)
+    }
+
+    /// Sets the section position in the settings editor && returns 'Self'
+    pub fn set_order(mut self, order: i64) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Adds the setting && returns 'Self' (the existing setting is replaced)
+    /// * key - the setting key (example: "myExt.enable")
+    /// * setting - the setting
+    pub fn add_setting<S>(mut self, key: S, setting: Setting) -> Self
+    where S: Into<String>
+    {
+        let key = key.into();
+        match self.properties.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, value)) => *value = setting,
+            None => self.properties.0.push((key, setting)),
+        }
+        self
+    }
+
+    /// Updates the setting && returns 'Self' (nothing is changed if the setting isn't found)
+    /// * key - the setting key with or without prefix (example: "myExt.enable" or "enable")
+    /// * update - the setting modifier
+    pub fn update<F>(mut self, key: &str, update: F) -> Self
+    where F: FnOnce(Setting) -> Setting
+    {
+        let found = self.properties.0
+            .iter_mut()
+            .find(|(k, _)| k == key || k.rsplit_once('.').is_some_and(|(_, name)| name == key));
+        if let Some((_, setting)) = found {
+            *setting = update(setting.clone());
+        }
+        self
+    }
+
+    /// Get the setting by its key
+    pub fn get(&self, key: &str) -> Option<&Setting> {
+        self.properties.0.iter().find(|(k, _)| k == key).map(|(_, s)| s)
+    }
+
+    /// Get the settings in insertion order, where <(KEY, SETTING)>
+    pub fn settings(&self) -> &[(String, Setting)] {
+        &self.properties.0
+    }
+
+    /// Checks the settings keys, types, defaults && enums
+    pub fn validate(&self) -> Result<()> {
+        for (key, setting) in &self.properties.0 {
+            let invalid = |error: String| Error::InvalidSetting { key: key.clone(), error };
+
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err(invalid("the key is invalid".into()));
+            }
+            setting.check().map_err(invalid)?;
+        }
+
+        Ok(())
+    }
+
+    /// Converts the section to JSON string
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self).map_err(Error::from)
+    }
+}
@@ -1,10 +1,22 @@
-use serde::Serialize;
-use std::path::PathBuf;
+use super::{ Keybinding, Configuration };
+use serde::{ Serialize, Deserialize };
+use std::{ collections::BTreeMap, path::{ Path, PathBuf }};
 
 /// The package contributes
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Contributes {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub snippets: Vec<SnippetsContribute>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub themes: Vec<ThemeContribute>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub grammars: Vec<GrammarContribute>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub languages: Vec<LanguageContribute>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub keybindings: Vec<Keybinding>,
+    #[serde(skip_serializing_if = "Vec::is_empty", deserialize_with = "de_configuration", default)]
+    pub configuration: Vec<Configuration>,
 }
 
 impl Contributes {
@@ -12,6 +24,11 @@ impl Contributes {
     pub fn new() -> Self {
         Self {
             snippets: vec![],
+            themes: vec![],
+            grammars: vec![],
+            languages: vec![],
+            keybindings: vec![],
+            configuration: vec![],
         }
     }
 
@@ -19,29 +36,182 @@ impl Contributes {
     pub fn reg_snippets(&mut self, contribute: SnippetsContribute) {
         self.snippets.push(contribute);
     }
+
+    /// Registrates a new color theme file
+    pub fn reg_theme(&mut self, contribute: ThemeContribute) {
+        self.themes.push(contribute);
+    }
+
+    /// Registrates a new grammar file
+    pub fn reg_grammar(&mut self, contribute: GrammarContribute) {
+        self.grammars.push(contribute);
+    }
+
+    /// Registrates a new language
+    pub fn reg_language(&mut self, contribute: LanguageContribute) {
+        self.languages.push(contribute);
+    }
+
+    /// Registrates a new keybinding
+    pub fn reg_keybinding(&mut self, keybinding: Keybinding) {
+        self.keybindings.push(keybinding);
+    }
+
+    /// Registrates a new settings section
+    pub fn reg_configuration(&mut self, config: Configuration) {
+        self.configuration.push(config);
+    }
+
+    /// Get the paths of all contributed files (without duplicates)
+    pub fn paths(&self) -> Vec<&Path> {
+        let mut paths: Vec<&Path> = vec![];
+        let snippets = self.snippets.iter().map(|c| c.path.as_path());
+        let themes = self.themes.iter().map(|c| c.path.as_path());
+        let grammars = self.grammars.iter().map(|c| c.path.as_path());
+        let languages = self.languages.iter().filter_map(|c| c.configuration.as_deref());
+        for path in snippets.chain(themes).chain(grammars).chain(languages) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        paths
+    }
+}
+
+/// Reads the settings sections from single object or array
+fn de_configuration<'de, D>(deserializer: D) -> Result<Vec<Configuration>, D::Error>
+where D: serde::Deserializer<'de>
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        One(Configuration),
+        Many(Vec<Configuration>),
+    }
+
+    Ok(match Repr::deserialize(deserializer)? {
+        Repr::One(config) => vec![config],
+        Repr::Many(configs) => configs,
+    })
+}
+
+
+impl std::default::Default for Contributes {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 
 /// The snippets contribute object
-/// * language - the snippets programming language
+/// * language - the snippets programming language (None for ".code-snippets" file which snippets have own scopes)
 /// * path - the snippets file path
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnippetsContribute {
-    pub language: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub language: Option<String>,
     pub path: PathBuf,
 }
 
 impl SnippetsContribute {
     /// Creates a new snippets contributes object
-    /// * lang - the snippets programming language
+    /// * lang - the snippets programming language (None for ".code-snippets" file which snippets have own scopes)
     /// * file_name - the file_name to the snippets file
-    pub fn new<S, P>(lang: S, file_name: P) -> Self
+    pub fn new<S, P>(lang: Option<S>, file_name: P) -> Self
     where S: Into<String>, P: Into<PathBuf> {
-        let path = PathBuf::from("snippets").join( &file_name.into() );
+        let path = PathBuf::from("snippets").join( file_name.into() );
         
         Self {
-            language: lang.into(),
+            language: lang.map(|v| v.into()),
             path,
         }
     }
 }
+
+
+/// The color theme contribute object
+/// * label - the theme name in theme picker
+/// * ui_theme - the base theme (example: "vs-dark")
+/// * path - the theme file path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeContribute {
+    pub label: String,
+    #[serde(rename = "uiTheme")]
+    pub ui_theme: String,
+    pub path: PathBuf,
+}
+
+impl ThemeContribute {
+    /// Creates a new color theme contributes object
+    /// * label - the theme name in theme picker
+    /// * ui_theme - the base theme (example: "vs-dark")
+    /// * file_name - the file name of the theme file
+    pub fn new<S, P>(label: S, ui_theme: S, file_name: P) -> Self
+    where S: Into<String>, P: Into<PathBuf> {
+        Self {
+            label: label.into(),
+            ui_theme: ui_theme.into(),
+            path: PathBuf::from("themes").join( file_name.into() ),
+        }
+    }
+}
+
+
+/// The grammar contribute object
+/// * language - the language id (None for injection grammar)
+/// * scope_name - the grammar root scope name
+/// * path - the grammar file path
+/// * embedded_languages - the embedded languages scopes, where <SCOPE_NAME, LANGUAGE_ID>
+/// * inject_to - the root scope names of grammars where the injection grammar is applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrammarContribute {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub language: Option<String>,
+    #[serde(rename = "scopeName")]
+    pub scope_name: String,
+    pub path: PathBuf,
+    #[serde(rename = "embeddedLanguages", skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub embedded_languages: BTreeMap<String, String>,
+    #[serde(rename = "injectTo", skip_serializing_if = "Vec::is_empty", default)]
+    pub inject_to: Vec<String>,
+}
+
+impl GrammarContribute {
+    /// Creates a new grammar contributes object
+    /// * language - the language id (None for injection grammar)
+    /// * scope_name - the grammar root scope name
+    /// * file_name - the file name of the grammar file
+    pub fn new<S, P>(language: Option<S>, scope_name: S, file_name: P) -> Self
+    where S: Into<String>, P: Into<PathBuf> {
+        Self {
+            language: language.map(|v| v.into()),
+            scope_name: scope_name.into(),
+            path: PathBuf::from("syntaxes").join( file_name.into() ),
+            embedded_languages: BTreeMap::new(),
+            inject_to: vec![],
+        }
+    }
+}
+
+
+/// The language contribute object
+/// * id - the language id
+/// * aliases - the language names shown in UI
+/// * extensions - the file extensions
+/// * filenames - the file names
+/// * first_line - the regex of the first file line
+/// * configuration - the language configuration file path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageContribute {
+    pub id: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub extensions: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub filenames: Vec<String>,
+    #[serde(rename = "firstLine", skip_serializing_if = "Option::is_none", default)]
+    pub first_line: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub configuration: Option<PathBuf>,
+}
@@ -0,0 +1,124 @@
+use super::{ Lint, LintKind };
+
+/// The diagnostic severity
+/// * Error - the marketplace rejects the package
+/// * Warning - the package is accepted, but something looks wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// The pre-publish diagnostic kind
+/// * InvalidId - the package id isn't lowercase or has the symbols except 'a-z', '0-9', '-'
+/// * MissingPublisher - the publisher isn't set
+/// * InvalidPublisher - the publisher has the symbols except 'a-z', 'A-Z', '0-9', '-'
+/// * PreReleaseVersion - the version has pre-release tag (the marketplace doesn't support it)
+/// * AnyEngine - the engines range accepts any VS Code version
+/// * MissingIcon - the icon file doesn't exist
+/// * SvgIcon - the icon is SVG image (the marketplace forbids it)
+/// * NonPngIcon - the icon isn't PNG image
+/// * InsecureRepository - the repository URL isn't HTTPS
+/// * InsecureReadmeImage - the README image isn't from HTTPS source
+/// * RelativeReadmeImage - the README has relative image link, but the repository isn't set
+/// * EmptyDescription - the description is empty
+/// * LongDescription - the description is too long
+/// * MissingContribution - the contributed file doesn't exist
+/// * MissingLicense - the LICENSE file doesn't exist
+/// * KeybindingConflict - the several keybindings have the same chords && overlapping 'when' clauses
+/// * SnippetLint - the snippets [lint](Lint) problem (duplicate names, prefixes, unknown variables, etc.)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    InvalidId,
+    MissingPublisher,
+    InvalidPublisher,
+    PreReleaseVersion,
+    AnyEngine,
+    MissingIcon,
+    SvgIcon,
+    NonPngIcon,
+    InsecureRepository,
+    InsecureReadmeImage,
+    RelativeReadmeImage,
+    EmptyDescription,
+    LongDescription,
+    MissingContribution,
+    MissingLicense,
+    KeybindingConflict,
+    SnippetLint(LintKind),
+}
+
+impl DiagnosticKind {
+    /// Get the diagnostic severity
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::AnyEngine | Self::NonPngIcon | Self::EmptyDescription | Self::LongDescription | Self::MissingLicense
+                | Self::KeybindingConflict | Self::SnippetLint(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// The package pre-publish diagnostic
+/// * kind - the diagnostic kind
+/// * value - the problem value (the id, path, URL, etc.)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub value: String,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic
+    pub fn new<S>(kind: DiagnosticKind, value: S) -> Self
+    where S: Into<String> {
+        Self { kind, value: value.into() }
+    }
+
+    /// Get the diagnostic severity
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+
+    /// Check the diagnostic is error
+    pub fn is_error(&self) -> bool {
+        self.severity() == Severity::Error
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let value = &self.value;
+        match self.severity() {
+            Severity::Error => write!(f, "error: ")?,
+            Severity::Warning => write!(f, "warning: ")?,
+        }
+
+        match self.kind {
+            DiagnosticKind::InvalidId => write!(f, "the package id '{value}' must be lowercase && contain only 'a-z', '0-9', '-'"),
+            DiagnosticKind::MissingPublisher => write!(f, "the publisher is not set"),
+            DiagnosticKind::InvalidPublisher => write!(f, "the publisher '{value}' must contain only 'a-z', 'A-Z', '0-9', '-'"),
+            DiagnosticKind::PreReleaseVersion => write!(f, "the marketplace doesn't support the pre-release version '{value}'"),
+            DiagnosticKind::AnyEngine => write!(f, "the engines range '{value}' accepts any VS Code version"),
+            DiagnosticKind::MissingIcon => write!(f, "the icon '{value}' is not found"),
+            DiagnosticKind::SvgIcon => write!(f, "the icon '{value}' can't be SVG image"),
+            DiagnosticKind::NonPngIcon => write!(f, "the icon '{value}' should be PNG image"),
+            DiagnosticKind::InsecureRepository => write!(f, "the repository URL '{value}' must use HTTPS"),
+            DiagnosticKind::InsecureReadmeImage => write!(f, "the README image '{value}' must come from HTTPS source"),
+            DiagnosticKind::RelativeReadmeImage => write!(f, "the README image '{value}' is relative, but the repository is not set"),
+            DiagnosticKind::EmptyDescription => write!(f, "the description is empty"),
+            DiagnosticKind::LongDescription => write!(f, "the description is longer than {value} symbols"),
+            DiagnosticKind::MissingContribution => write!(f, "the contributed file '{value}' is not found"),
+            DiagnosticKind::MissingLicense => write!(f, "the LICENSE file is not found"),
+            DiagnosticKind::KeybindingConflict => write!(f, "the keybindings {value} have overlapping 'when' clauses"),
+            DiagnosticKind::SnippetLint(_) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<Lint> for Diagnostic {
+    /// Creates the warning from snippets lint (the value is the lint message)
+    fn from(lint: Lint) -> Self {
+        Self::new(DiagnosticKind::SnippetLint(lint.kind), lint.to_string())
+    }
+}
@@ -1,15 +1,17 @@
-use super::Version;
-use serde::Serialize;
+use super::{ Version, VersionReq };
+use serde::{ Serialize, Deserialize };
 
 /// The package engines option
-#[derive(Debug, Clone, Serialize)]
+/// * vscode - the compatible VS Code versions range (example: "^1.90.0")
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Engines {
-    pub vscode: Version,
+    pub vscode: VersionReq,
 }
 
 impl Engines {
     /// Creates a new package engines option
-    pub fn new(vscode_ver: Version) -> Self {
+    /// * vscode_ver - the compatible VS Code versions range
+    pub fn new(vscode_ver: VersionReq) -> Self {
         Self {
             vscode: vscode_ver,
         }
@@ -19,7 +21,7 @@ impl Engines {
 impl std::default::Default for Engines {
     fn default() -> Self {
         Self {
-            vscode: "^1.90.0".parse().unwrap()
+            vscode: VersionReq::caret(Version::new(1, 90, 0))
         }
     }
 }
@@ -0,0 +1,41 @@
+use serde::{ Serialize, Deserialize };
+
+/// The marketplace page banner
+/// * color - the banner background color (example: "#C80000")
+/// * theme - the banner text theme
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GalleryBanner {
+    pub color: String,
+    pub theme: BannerTheme,
+}
+
+impl GalleryBanner {
+    /// Creates a new marketplace page banner
+    /// * color - the banner background color (example: "#C80000")
+    /// * theme - the banner text theme
+    pub fn new<S>(color: S, theme: BannerTheme) -> Self
+    where S: Into<String> {
+        Self {
+            color: color.into(),
+            theme,
+        }
+    }
+}
+
+/// The marketplace page banner theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BannerTheme {
+    Dark,
+    Light,
+}
+
+impl BannerTheme {
+    /// Get the theme name (example: "dark")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+        }
+    }
+}
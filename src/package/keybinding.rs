@@ -0,0 +1,338 @@
+use crate::prelude::*;
+use serde::{ Serialize, Deserialize };
+
+/// The keybinding platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Platform {
+    Mac,
+    Linux,
+    Windows,
+}
+
+impl Platform {
+    /// The all platforms
+    pub const ALL: [Self; 3] = [Self::Mac, Self::Linux, Self::Windows];
+
+    /// Get the platform key in keybinding (example: "mac")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mac => "mac",
+            Self::Linux => "linux",
+            Self::Windows => "win",
+        }
+    }
+}
+
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+
+/// The key chord (the key with modifiers pressed at once)
+/// * ctrl, shift, alt - the modifier flags
+/// * meta - the "cmd" (or "meta", "win") modifier flag
+/// * key - the key name in lowercase (example: "k", "f5", "pageup", "[KeyA]")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChord {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool,
+    pub key: String,
+}
+
+impl KeyChord {
+    /// The named keys (the letters, digits && "f1" - "f19" are allowed too)
+    pub const KEYS: &'static [&'static str] = &[
+        "`", "-", "=", "[", "]", "\\", ";", "'", ",", ".", "/",
+        "left", "up", "right", "down", "pageup", "pagedown", "end", "home",
+        "tab", "enter", "escape", "space", "backspace", "delete", "pausebreak", "capslock", "insert",
+        "numpad0", "numpad1", "numpad2", "numpad3", "numpad4", "numpad5", "numpad6", "numpad7", "numpad8", "numpad9",
+        "numpad_multiply", "numpad_add", "numpad_separator", "numpad_subtract", "numpad_decimal", "numpad_divide",
+    ];
+
+    /// Check the key name is known (example: "a", "f12", "pagedown", "[KeyA]")
+    pub fn is_key(key: &str) -> bool {
+        let is_fn = key.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()).is_some_and(|n| (1..=19).contains(&n));
+        let is_scan_code = key.len() > 2 && key.starts_with('[') && key.ends_with(']') && key[1..key.len() - 1].chars().all(|c| c.is_ascii_alphanumeric());
+        let is_char = key.len() == 1 && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+
+        is_char || is_fn || is_scan_code || Self::KEYS.contains(&key)
+    }
+}
+
+impl std::str::FromStr for KeyChord {
+    type Err = String;
+
+    /// Parses the chord from string (example: "ctrl+shift+k")
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut this = Self { ctrl: false, shift: false, alt: false, meta: false, key: String::new() };
+        let parts = s.split('+').collect::<Vec<_>>();
+        let (key, modifiers) = parts.split_last().unwrap_or((&"", &[]));
+
+        for modifier in modifiers {
+            let flag = match modifier.to_lowercase().as_str() {
+                "ctrl" => &mut this.ctrl,
+                "shift" => &mut this.shift,
+                "alt" => &mut this.alt,
+                "cmd" | "meta" | "win" => &mut this.meta,
+                "" => return Err(format!("the chord '{s}' has empty modifier")),
+                other => return Err(format!("the modifier '{other}' is unknown")),
+            };
+            if *flag {
+                return Err(format!("the chord '{s}' has duplicate modifier '{modifier}'"));
+            }
+            *flag = true;
+        }
+
+        let key = match key.starts_with('[') { true => key.to_string(), false => key.to_lowercase() };
+        if !Self::is_key(&key) {
+            return Err(format!("the key '{key}' is unknown"));
+        }
+        this.key = key;
+
+        Ok(this)
+    }
+}
+
+impl std::fmt::Display for KeyChord {
+    /// Writes the chord in canonical form (example: "ctrl+shift+alt+meta+k")
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (flag, name) in [(self.ctrl, "ctrl"), (self.shift, "shift"), (self.alt, "alt"), (self.meta, "meta")] {
+            if flag { write!(f, "{name}+")?; }
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+
+/// The keybinding (the 'contributes.keybindings' entry)
+/// * key - the default key chords (example: "ctrl+k ctrl+c")
+/// * mac, linux, win - the platform specific key chords
+/// * command - the command id (example: "editor.action.addCommentLine")
+/// * args - the command arguments
+/// * when - the condition when the keybinding is active (example: "editorTextFocus && !editorReadonly")
+/// * description - the keybinding description (written to README only)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keybinding {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub mac: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub linux: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub win: Option<String>,
+    pub command: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub args: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub when: Option<String>,
+    #[serde(skip)]
+    pub description: Option<String>,
+}
+
+impl Keybinding {
+    /// Creates a new keybinding
+    /// * key - the key chords (example: "ctrl+k ctrl+c")
+    /// * command - the command id (example: "editor.action.addCommentLine")
+    pub fn new<S>(key: S, command: S) -> Self
+    where S: Into<String>
+    {
+        Self {
+            key: key.into(),
+            mac: None,
+            linux: None,
+            win: None,
+            command: command.into(),
+            args: None,
+            when: None,
+            description: None,
+        }
+    }
+
+    /// Sets the macOS key chords && returns 'Self'
+    pub fn set_mac<S>(mut self, key: S) -> Self
+    where S: Into<String> {
+        self.mac = Some(key.into());
+        self
+    }
+
+    /// Sets the Linux key chords && returns 'Self'
+    pub fn set_linux<S>(mut self, key: S) -> Self
+    where S: Into<String> {
+        self.linux = Some(key.into());
+        self
+    }
+
+    /// Sets the Windows key chords && returns 'Self'
+    pub fn set_win<S>(mut self, key: S) -> Self
+    where S: Into<String> {
+        self.win = Some(key.into());
+        self
+    }
+
+    /// Sets the command arguments && returns 'Self'
+    /// * args - the arguments (example: `json!({ "text": "TODO" })`)
+    pub fn set_args(mut self, args: serde_json::Value) -> Self {
+        self.args = Some(args);
+        self
+    }
+
+    /// Sets the condition when the keybinding is active && returns 'Self'
+    /// * when - the when clause (example: "editorTextFocus && editorLangId == rust")
+    pub fn set_when<S>(mut self, when: S) -> Self
+    where S: Into<String> {
+        self.when = Some(when.into());
+        self
+    }
+
+    /// Sets the keybinding description && returns 'Self'
+    pub fn set_descr<S>(mut self, descr: S) -> Self
+    where S: Into<String> {
+        self.description = Some(descr.into());
+        self
+    }
+
+    /// Get the key chords used on platform (the platform specific key or the default key)
+    pub fn key_for(&self, platform: Platform) -> &str {
+        let key = match platform {
+            Platform::Mac => &self.mac,
+            Platform::Linux => &self.linux,
+            Platform::Windows => &self.win,
+        };
+        key.as_deref().unwrap_or(&self.key)
+    }
+
+    /// Parses the key chords used on platform
+    pub fn chords(&self, platform: Platform) -> std::result::Result<Vec<KeyChord>, String> {
+        let key = self.key_for(platform);
+        let chords = key.split_whitespace().map(str::parse).collect::<std::result::Result<Vec<KeyChord>, _>>()?;
+        match chords.is_empty() {
+            true => Err("the key is empty".into()),
+            false => Ok(chords),
+        }
+    }
+
+    /// Checks the key chords syntax of all platforms && the command id
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |error: String| Error::InvalidKeybinding { key: self.key.clone(), error };
+
+        if self.command.trim().is_empty() {
+            return Err(invalid("the command is empty".into()));
+        }
+        for platform in Platform::ALL {
+            self.chords(platform).map_err(|e| invalid(format!("{platform}: {e}")))?;
+        }
+
+        Ok(())
+    }
+
+    /// Finds the keybindings which have the same chords && overlapping 'when' clauses
+    /// (the invalid key chords are skipped)
+    /// * bindings - the keybindings list
+    pub fn conflicts(bindings: &[Keybinding]) -> Vec<KeybindingConflict> {
+        let mut conflicts: Vec<KeybindingConflict> = vec![];
+
+        for platform in Platform::ALL {
+            let chords = bindings.iter().map(|b| b.chords(platform).ok()).collect::<Vec<_>>();
+
+            for (i, a) in bindings.iter().enumerate() {
+                for (j, b) in bindings.iter().enumerate().skip(i + 1) {
+                    let (Some(ca), Some(cb)) = (&chords[i], &chords[j]) else { continue };
+                    if ca != cb || !when_overlaps(a.when.as_deref(), b.when.as_deref()) {
+                        continue;
+                    }
+
+                    let key = ca.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ");
+                    let commands = vec![a.command.clone(), b.command.clone()];
+                    match conflicts.iter_mut().find(|c| c.key == key && c.commands == commands) {
+                        Some(conflict) if !conflict.platforms.contains(&platform) => conflict.platforms.push(platform),
+                        Some(_) => {}
+                        None => conflicts.push(KeybindingConflict { key, platforms: vec![platform], commands }),
+                    }
+                }
+            }
+        }
+
+        conflicts
+    }
+}
+
+
+/// The keybindings conflict
+/// * key - the conflicting chords in canonical form (example: "ctrl+k ctrl+c")
+/// * platforms - the platforms where the keybindings conflict
+/// * commands - the conflicting commands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeybindingConflict {
+    pub key: String,
+    pub platforms: Vec<Platform>,
+    pub commands: Vec<String>,
+}
+
+impl std::fmt::Display for KeybindingConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let platforms = self.platforms.iter().map(|p| p.as_str()).collect::<Vec<_>>().join(", ");
+        write!(f, "'{}' [{platforms}]: {}", self.key, self.commands.join(", "))
+    }
+}
+
+
+/// The 'when' clause term
+#[derive(Debug, Clone, PartialEq)]
+enum Term<'a> {
+    Is(&'a str),
+    Not(&'a str),
+    Eq(&'a str, &'a str),
+    Ne(&'a str, &'a str),
+    Other,
+}
+
+impl<'a> Term<'a> {
+    /// Parses the term (example: "!editorReadonly", "editorLangId == rust")
+    fn parse(s: &'a str) -> Self {
+        let unquote = |v: &'a str| v.trim().trim_matches('\'');
+        let s = s.trim();
+
+        if let Some((key, value)) = s.split_once("==") {
+            Self::Eq(key.trim(), unquote(value))
+        } else if let Some((key, value)) = s.split_once("!=") {
+            Self::Ne(key.trim(), unquote(value))
+        } else if let Some(key) = s.strip_prefix('!') {
+            Self::Not(key.trim())
+        } else if s.chars().all(|c| c.is_alphanumeric() || "._-:".contains(c)) {
+            Self::Is(s)
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Check the terms can't be true at the same time
+    fn contradicts(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Is(a), Self::Not(b)) | (Self::Not(a), Self::Is(b)) => a == b,
+            (Self::Eq(a, x), Self::Eq(b, y)) => a == b && x != y,
+            (Self::Eq(a, x), Self::Ne(b, y)) | (Self::Ne(a, x), Self::Eq(b, y)) => a == b && x == y,
+            _ => false,
+        }
+    }
+}
+
+/// Parses the 'when' clause to disjunction of conjunctions (None if the clause is empty or has parentheses)
+fn when_terms(when: Option<&str>) -> Option<Vec<Vec<Term<'_>>>> {
+    let when = when?.trim();
+    if when.is_empty() || when.contains(['(', ')']) {
+        return None;
+    }
+    Some(when.split("||").map(|d| d.split("&&").map(Term::parse).collect()).collect())
+}
+
+/// Check the 'when' clauses could be true at the same time (the clauses with parentheses are assumed overlapping)
+fn when_overlaps(a: Option<&str>, b: Option<&str>) -> bool {
+    let (Some(a), Some(b)) = (when_terms(a), when_terms(b)) else { return true };
+    a.iter().any(|da| b.iter().any(|db| {
+        !da.iter().any(|ta| db.iter().any(|tb| ta.contradicts(tb)))
+    }))
+}
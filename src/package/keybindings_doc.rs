@@ -0,0 +1,68 @@
+use crate::tools::markdown_table;
+use super::Keybinding;
+
+/// The keybindings documentation generator
+/// * title - the documentation title
+/// * body - the documentation body, where '<(KEY, MAC_KEY, COMMAND, DESCRIPTION)>'
+#[derive(Debug, Clone)]
+pub struct KeybindingsDoc {
+    pub title: String,
+    pub body: Vec<(String, String, String, String)>,
+}
+
+impl KeybindingsDoc {
+    /// Creates a new 'KeybindingsDoc' object
+    /// * title - the documentation title
+    pub fn new<S>(title: S) -> Self
+    where S: Into<String> {
+        Self { title: title.into(), body: vec![] }
+    }
+
+    /// Generates the documentation of keybindings list
+    /// * bindings - the keybindings
+    pub fn from_keybindings(bindings: &[Keybinding]) -> Self {
+        let mut doc = Self::new("Keybindings");
+        for kb in bindings {
+            doc.write_line(kb);
+        }
+        doc
+    }
+
+    /// Writing a new line to documentation body
+    /// * kb - the keybinding
+    pub fn write_line(&mut self, kb: &Keybinding) {
+        self.body.push((
+            format!("`{}`", kb.key),
+            kb.mac.as_ref().map(|k| format!("`{k}`")).unwrap_or_default(),
+            format!("`{}`", kb.command),
+            kb.description.clone().unwrap_or_default(),
+        ));
+    }
+}
+
+impl std::fmt::Display for KeybindingsDoc {
+    /// Converts the documentation object to string (the "Mac:" column is added if any keybinding has the macOS key)
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let has_mac = self.body.iter().any(|(_, mac, _, _)| !mac.is_empty());
+        let rows = self.body
+            .iter()
+            .map(|(key, mac, command, descr)| match has_mac {
+                true => vec![key, mac, command, descr],
+                false => vec![key, command, descr],
+            })
+            .collect::<Vec<_>>();
+        let headers: &[&str] = match has_mac {
+            true => &["Key:", "Mac:", "Command:", "Description:"],
+            false => &["Key:", "Command:", "Description:"],
+        };
+
+        write!(f, "# {}:\n{}", self.title, markdown_table(headers, &rows))
+    }
+}
+
+impl From<KeybindingsDoc> for String {
+    /// Converts the documentation object to string
+    fn from(doc: KeybindingsDoc) -> Self {
+        doc.to_string()
+    }
+}
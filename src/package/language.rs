@@ -0,0 +1,156 @@
+use crate::prelude::*;
+use crate::OutputSink;
+use super::{ LanguageConfiguration, LanguageContribute };
+use std::path::{ Path, PathBuf };
+
+/// The language definition (the 'contributes.languages' entry && its "language-configuration.json")
+/// * id - the language id (example: "mydsl")
+/// * aliases - the language names shown in UI (example: ["MyDSL"])
+/// * extensions - the file extensions (example: [".mydsl"])
+/// * filenames - the file names (example: ["Mydslfile"])
+/// * first_line - the regex of the first file line (example: "^#!/usr/bin/env mydsl")
+/// * configuration - the language [configuration](LanguageConfiguration)
+/// * file_name - the configuration file name
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageDefinition {
+    pub id: String,
+    pub aliases: Vec<String>,
+    pub extensions: Vec<String>,
+    pub filenames: Vec<String>,
+    pub first_line: Option<String>,
+    pub configuration: LanguageConfiguration,
+    pub file_name: PathBuf,
+}
+
+impl LanguageDefinition {
+    /// Creates a new language definition
+    /// * id - the language id (example: "mydsl")
+    /// * alias - the language name shown in UI (example: "MyDSL")
+    pub fn new<S>(id: S, alias: S) -> Self
+    where S: Into<String>
+    {
+        let id = id.into();
+        let file_name = format!("{}.language-configuration.json", to_latin_text(&id.to_lowercase(), true)).into();
+
+        Self {
+            id,
+            aliases: vec![alias.into()],
+            extensions: vec![],
+            filenames: vec![],
+            first_line: None,
+            configuration: LanguageConfiguration::new(),
+            file_name,
+        }
+    }
+
+    /// Adds the language name shown in UI && returns 'Self'
+    pub fn add_alias<S>(mut self, alias: S) -> Self
+    where S: Into<String> {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Adds the file extension && returns 'Self'
+    /// * extension - the file extension with dot (example: ".mydsl")
+    pub fn add_extension<S>(mut self, extension: S) -> Self
+    where S: Into<String> {
+        self.extensions.push(extension.into());
+        self
+    }
+
+    /// Adds the file name && returns 'Self'
+    /// * filename - the full file name (example: "Mydslfile")
+    pub fn add_filename<S>(mut self, filename: S) -> Self
+    where S: Into<String> {
+        self.filenames.push(filename.into());
+        self
+    }
+
+    /// Sets the regex of the first file line && returns 'Self'
+    pub fn set_first_line<S>(mut self, regex: S) -> Self
+    where S: Into<String> {
+        self.first_line = Some(regex.into());
+        self
+    }
+
+    /// Sets the language configuration && returns 'Self'
+    pub fn set_configuration(mut self, config: LanguageConfiguration) -> Self {
+        self.configuration = config;
+        self
+    }
+
+    /// Sets the configuration file name && returns 'Self'
+    pub fn set_file_name<P>(mut self, file_name: P) -> Self
+    where P: Into<PathBuf> {
+        self.file_name = file_name.into();
+        self
+    }
+
+    /// Get the configuration file path (None if the configuration is empty)
+    pub fn configuration_path(&self) -> Option<PathBuf> {
+        (!self.configuration.is_empty()).then(|| Path::new("languages").join(&self.file_name))
+    }
+
+    /// Generates the 'contributes.languages' entry
+    pub fn contribute(&self) -> LanguageContribute {
+        LanguageContribute {
+            id: self.id.clone(),
+            aliases: self.aliases.clone(),
+            extensions: self.extensions.clone(),
+            filenames: self.filenames.clone(),
+            first_line: self.first_line.clone(),
+            configuration: self.configuration_path(),
+        }
+    }
+
+    /// Creates the language definition from 'contributes.languages' entry
+    /// * contrib - the contributed language
+    /// * config - the language configuration
+    pub fn from_contribute(contrib: &LanguageContribute, config: LanguageConfiguration) -> Self {
+        let mut this = Self::new(contrib.id.as_str(), "");
+        this.aliases = contrib.aliases.clone();
+        this.extensions = contrib.extensions.clone();
+        this.filenames = contrib.filenames.clone();
+        this.first_line = contrib.first_line.clone();
+        this.configuration = config;
+        if let Some(file_name) = contrib.configuration.as_ref().and_then(|p| p.file_name()) {
+            this.file_name = file_name.into();
+        }
+        this
+    }
+
+    /// Checks the language id, file extensions && configuration regexes
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |error: String| Err(Error::InvalidLanguage { id: self.id.clone(), error });
+
+        if self.id.is_empty() || self.id.contains(char::is_whitespace) {
+            return invalid(format!("the language id '{}' is invalid", self.id));
+        }
+        if let Some(ext) = self.extensions.iter().find(|e| !e.starts_with('.')) {
+            return invalid(format!("the extension '{ext}' must start with dot"));
+        }
+        if let Some(regex) = &self.first_line {
+            if let Err(e) = fancy_regex::Regex::new(regex) {
+                return invalid(format!("firstLine: the regex '{regex}' is invalid: {e}"));
+            }
+        }
+
+        self.configuration.check().or_else(invalid)
+    }
+
+    /// Writes the configuration file "languages/<FILE_NAME>" to output (nothing is written if the configuration is empty)
+    /// * sink - the package output
+    pub fn write_into(&self, sink: &mut dyn OutputSink) -> Result<()> {
+        match self.configuration_path() {
+            Some(path) => sink.write_file(&path, self.configuration.to_json()?.as_bytes()),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes the configuration file to "%DIR/languages/%FILE_NAME"
+    /// * dir - the package root directory
+    pub fn write_to<P>(&self, dir: P) -> Result<()>
+    where P: Into<PathBuf> {
+        self.write_into(&mut crate::FsSink::new(dir))
+    }
+}
@@ -0,0 +1,372 @@
+use crate::prelude::*;
+use super::OnEnterRule;
+use serde::{ Serialize, Deserialize };
+
+/// The language comment tokens
+/// * line_comment - the line comment token (example: "//")
+/// * block_comment - the block comment tokens (example: ("/*", "*/"))
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Comments {
+    #[serde(rename = "lineComment", skip_serializing_if = "Option::is_none", default)]
+    pub line_comment: Option<String>,
+    #[serde(rename = "blockComment", skip_serializing_if = "Option::is_none", default)]
+    pub block_comment: Option<(String, String)>,
+}
+
+/// The auto closed characters pair
+/// * open - the opening characters (example: "{")
+/// * close - the closing characters (example: "}")
+/// * not_in - the scopes where the pair isn't closed: "string", "comment"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AutoClosingPair {
+    pub open: String,
+    pub close: String,
+    #[serde(rename = "notIn", skip_serializing_if = "Vec::is_empty")]
+    pub not_in: Vec<String>,
+}
+
+impl<'de> Deserialize<'de> for AutoClosingPair {
+    /// Reads the pair from array ["{", "}"] or object { "open": "{", "close": "}", "notIn": [...] }
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Pair(String, String),
+            Object {
+                open: String,
+                close: String,
+                #[serde(rename = "notIn", default)]
+                not_in: Vec<String>,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Pair(open, close) => Self { open, close, not_in: vec![] },
+            Repr::Object { open, close, not_in } => Self { open, close, not_in },
+        })
+    }
+}
+
+/// The configuration regex (JavaScript syntax)
+/// * pattern - the regex pattern (example: r"^\s*//\s*#region\b")
+/// * flags - the JavaScript regex flags (example: "i")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigRegex {
+    pub pattern: String,
+    pub flags: Option<String>,
+}
+
+impl ConfigRegex {
+    /// The JavaScript regex flags
+    pub const FLAGS: &'static str = "dgimsuvy";
+
+    /// Creates a new regex without flags
+    /// * pattern - the regex pattern
+    pub fn new<S>(pattern: S) -> Self
+    where S: Into<String> {
+        Self { pattern: pattern.into(), flags: None }
+    }
+
+    /// Sets the regex flags && returns 'Self'
+    /// * flags - the JavaScript regex flags (example: "iu")
+    pub fn flags<S>(mut self, flags: S) -> Self
+    where S: Into<String> {
+        self.flags = Some(flags.into());
+        self
+    }
+
+    /// Checks the regex pattern && flags, returns the error message
+    pub fn check(&self) -> std::result::Result<(), String> {
+        let flags = self.flags.as_deref().unwrap_or_default();
+        if let Some(flag) = flags.chars().find(|c| !Self::FLAGS.contains(*c)) {
+            return Err(format!("the regex '{}' has unknown flag '{flag}'", self.pattern));
+        }
+
+        // the flags which change the matching are applied as inline modifiers:
+        let inline: String = flags.chars().filter(|c| "ims".contains(*c)).collect();
+        let regex = if inline.is_empty() { self.pattern.clone() } else { format!("(?{inline}){}", self.pattern) };
+        fancy_regex::Regex::new(&regex)
+            .map(|_| ())
+            .map_err(|e| format!("the regex '{}' is invalid: {e}", self.pattern))
+    }
+}
+
+impl From<String> for ConfigRegex {
+    fn from(pattern: String) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl From<&str> for ConfigRegex {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl Serialize for ConfigRegex {
+    /// Writes the regex as string "..." or object { "pattern": "...", "flags": "..." } if the flags are set
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where S: serde::Serializer
+    {
+        use serde::ser::SerializeStruct;
+
+        match &self.flags {
+            None => serializer.serialize_str(&self.pattern),
+            Some(flags) => {
+                let mut object = serializer.serialize_struct("ConfigRegex", 2)?;
+                object.serialize_field("pattern", &self.pattern)?;
+                object.serialize_field("flags", flags)?;
+                object.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ConfigRegex {
+    /// Reads the regex from string "..." or object { "pattern": "...", "flags": "..." }
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Pattern(String),
+            Object {
+                pattern: String,
+                #[serde(default)]
+                flags: Option<String>,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Pattern(pattern) => Self { pattern, flags: None },
+            Repr::Object { pattern, flags } => Self { pattern, flags },
+        })
+    }
+}
+
+/// The folding regions settings
+/// * off_side - the indentation based folding flag (the empty lines belong to the next block)
+/// * markers - the folding region markers
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Folding {
+    #[serde(rename = "offSide", skip_serializing_if = "Option::is_none", default)]
+    pub off_side: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub markers: Option<FoldingMarkers>,
+}
+
+/// The folding region markers
+/// * start - the regex of region start (example: r"^\s*//\s*#region\b")
+/// * end - the regex of region end (example: r"^\s*//\s*#endregion\b")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FoldingMarkers {
+    pub start: ConfigRegex,
+    pub end: ConfigRegex,
+}
+
+/// The indentation rules
+/// * increase_indent_pattern - the regex of line after which the next line is indented
+/// * decrease_indent_pattern - the regex of line which is outdented
+/// * indent_next_line_pattern - the regex of line after which only the next line is indented
+/// * unindented_line_pattern - the regex of line which indentation is ignored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndentationRules {
+    #[serde(rename = "increaseIndentPattern")]
+    pub increase_indent_pattern: ConfigRegex,
+    #[serde(rename = "decreaseIndentPattern")]
+    pub decrease_indent_pattern: ConfigRegex,
+    #[serde(rename = "indentNextLinePattern", skip_serializing_if = "Option::is_none", default)]
+    pub indent_next_line_pattern: Option<ConfigRegex>,
+    #[serde(rename = "unIndentedLinePattern", skip_serializing_if = "Option::is_none", default)]
+    pub unindented_line_pattern: Option<ConfigRegex>,
+}
+
+
+/// The language configuration (the file "language-configuration.json")
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LanguageConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub comments: Option<Comments>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub brackets: Vec<(String, String)>,
+    #[serde(rename = "autoClosingPairs", skip_serializing_if = "Vec::is_empty", default)]
+    pub auto_closing_pairs: Vec<AutoClosingPair>,
+    #[serde(rename = "surroundingPairs", skip_serializing_if = "Vec::is_empty", default)]
+    pub surrounding_pairs: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub folding: Option<Folding>,
+    #[serde(rename = "wordPattern", skip_serializing_if = "Option::is_none", default)]
+    pub word_pattern: Option<ConfigRegex>,
+    #[serde(rename = "indentationRules", skip_serializing_if = "Option::is_none", default)]
+    pub indentation_rules: Option<IndentationRules>,
+    #[serde(rename = "onEnterRules", skip_serializing_if = "Vec::is_empty", default)]
+    pub on_enter_rules: Vec<OnEnterRule>,
+}
+
+impl LanguageConfiguration {
+    /// The scopes where the auto closing pairs could be disabled
+    pub const NOT_IN_SCOPES: [&'static str; 2] = ["string", "comment"];
+
+    /// Creates an empty language configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check the configuration is empty
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Sets the line comment token && returns 'Self'
+    /// * token - the line comment token (example: "//")
+    pub fn set_line_comment<S>(mut self, token: S) -> Self
+    where S: Into<String> {
+        self.comments.get_or_insert_with(Comments::default).line_comment = Some(token.into());
+        self
+    }
+
+    /// Sets the block comment tokens && returns 'Self'
+    /// * start - the comment start token (example: "/*")
+    /// * end - the comment end token (example: "*/")
+    pub fn set_block_comment<S>(mut self, start: S, end: S) -> Self
+    where S: Into<String> {
+        self.comments.get_or_insert_with(Comments::default).block_comment = Some((start.into(), end.into()));
+        self
+    }
+
+    /// Adds the brackets pair (used for matching && indentation) && returns 'Self'
+    pub fn add_bracket<S>(mut self, open: S, close: S) -> Self
+    where S: Into<String> {
+        self.brackets.push((open.into(), close.into()));
+        self
+    }
+
+    /// Adds the auto closed pair && returns 'Self'
+    /// * not_in - the scopes where the pair isn't closed: "string", "comment"
+    pub fn add_auto_closing_pair<S>(mut self, open: S, close: S, not_in: &[&str]) -> Self
+    where S: Into<String> {
+        self.auto_closing_pairs.push(AutoClosingPair {
+            open: open.into(),
+            close: close.into(),
+            not_in: not_in.iter().map(|s| s.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Adds the pair which surrounds the selected text && returns 'Self'
+    pub fn add_surrounding_pair<S>(mut self, open: S, close: S) -> Self
+    where S: Into<String> {
+        self.surrounding_pairs.push((open.into(), close.into()));
+        self
+    }
+
+    /// Sets the folding region markers && returns 'Self'
+    /// * start - the regex of region start (example: r"^\s*//\s*#region\b")
+    /// * end - the regex of region end (example: r"^\s*//\s*#endregion\b")
+    pub fn set_folding_markers<S>(mut self, start: S, end: S) -> Self
+    where S: Into<ConfigRegex> {
+        self.folding.get_or_insert_with(Folding::default).markers = Some(FoldingMarkers { start: start.into(), end: end.into() });
+        self
+    }
+
+    /// Sets the indentation based folding flag && returns 'Self'
+    pub fn set_off_side(mut self, off_side: bool) -> Self {
+        self.folding.get_or_insert_with(Folding::default).off_side = Some(off_side);
+        self
+    }
+
+    /// Sets the regex of the word && returns 'Self'
+    /// * regex - the pattern string or the [regex](ConfigRegex) with flags
+    pub fn set_word_pattern<S>(mut self, regex: S) -> Self
+    where S: Into<ConfigRegex> {
+        self.word_pattern = Some(regex.into());
+        self
+    }
+
+    /// Sets the indentation rules && returns 'Self'
+    /// * increase - the regex of line after which the next line is indented
+    /// * decrease - the regex of line which is outdented
+    pub fn set_indentation_rules<S>(mut self, increase: S, decrease: S) -> Self
+    where S: Into<ConfigRegex> {
+        self.indentation_rules = Some(IndentationRules {
+            increase_indent_pattern: increase.into(),
+            decrease_indent_pattern: decrease.into(),
+            indent_next_line_pattern: None,
+            unindented_line_pattern: None,
+        });
+        self
+    }
+
+    /// Adds the rule applied on Enter press && returns 'Self'
+    pub fn add_on_enter_rule(mut self, rule: OnEnterRule) -> Self {
+        self.on_enter_rules.push(rule);
+        self
+    }
+
+    /// Get the all configuration regexes, where <(JSON_PATH, REGEX)>
+    pub fn regexes(&self) -> Vec<(String, &ConfigRegex)> {
+        let mut regexes = vec![];
+
+        if let Some(markers) = self.folding.as_ref().and_then(|f| f.markers.as_ref()) {
+            regexes.push(("folding.markers.start".into(), &markers.start));
+            regexes.push(("folding.markers.end".into(), &markers.end));
+        }
+        if let Some(word) = &self.word_pattern {
+            regexes.push(("wordPattern".into(), word));
+        }
+        if let Some(rules) = &self.indentation_rules {
+            regexes.push(("indentationRules.increaseIndentPattern".into(), &rules.increase_indent_pattern));
+            regexes.push(("indentationRules.decreaseIndentPattern".into(), &rules.decrease_indent_pattern));
+            if let Some(regex) = &rules.indent_next_line_pattern {
+                regexes.push(("indentationRules.indentNextLinePattern".into(), regex));
+            }
+            if let Some(regex) = &rules.unindented_line_pattern {
+                regexes.push(("indentationRules.unIndentedLinePattern".into(), regex));
+            }
+        }
+        for (i, rule) in self.on_enter_rules.iter().enumerate() {
+            for (key, regex) in rule.regexes() {
+                regexes.push((format!("onEnterRules[{i}].{key}"), regex));
+            }
+        }
+
+        regexes
+    }
+
+    /// Checks the configuration regexes && pairs, returns the error message
+    pub fn check(&self) -> std::result::Result<(), String> {
+        for (path, regex) in self.regexes() {
+            regex.check().map_err(|e| format!("{path}: {e}"))?;
+        }
+
+        let pairs = self.brackets.iter().chain(&self.surrounding_pairs).map(|(o, c)| (o.as_str(), c.as_str()));
+        let pairs = pairs.chain(self.auto_closing_pairs.iter().map(|p| (p.open.as_str(), p.close.as_str())));
+        for (open, close) in pairs {
+            if open.is_empty() || close.is_empty() {
+                return Err(format!("the pair '{open}' '{close}' has empty token"));
+            }
+        }
+
+        for pair in &self.auto_closing_pairs {
+            if let Some(scope) = pair.not_in.iter().find(|s| !Self::NOT_IN_SCOPES.contains(&s.as_str())) {
+                return Err(format!("the auto closing pair '{}' has unknown scope '{scope}'", pair.open));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts the configuration to JSON string
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self).map_err(Error::from)
+    }
+
+    /// Imports the configuration from JSON (the comments && trailing commas are allowed)
+    /// * json - the "language-configuration.json" file contents
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(&strip_json_comments(json)).map_err(Error::from)
+    }
+}
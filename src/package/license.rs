@@ -1,8 +1,9 @@
 use crate::prelude::*;
 use chrono::{Datelike, Utc};
-use std::{ path::PathBuf, fs };
+use crate::OutputSink;
+use std::path::{ Path, PathBuf };
 
-const LICENSE_MIT: &'static str = r###"MIT License
+const LICENSE_MIT: &str = r###"MIT License
 
 Copyright (c) %[YEAR] %[AUTHOR]
 
@@ -24,7 +25,7 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.""###;
 
-const LICENSE_APACHE2: &'static str = r###"                                 Apache License
+const LICENSE_APACHE2: &str = r###"                                 Apache License
                            Version 2.0, January 2004
                         http://www.apache.org/licenses/
 
@@ -230,6 +231,7 @@ const LICENSE_APACHE2: &'static str = r###"                                 Apac
 /// The package LICENSE generator
 #[derive(Debug, Clone)]
 pub struct License {
+    id: String,
     value: String,
 }
 
@@ -238,6 +240,7 @@ impl License {
     /// & author - the package author full name
     pub fn mit(author: &str) -> Self {
         Self {
+            id: "MIT".to_owned(),
             value: LICENSE_MIT
                 .replace("%[AUTHOR]", author)
                 .replace("%[YEAR]", &Utc::now().year().to_string())
@@ -248,19 +251,43 @@ impl License {
     /// & author - the package author full name
     pub fn apache2(author: &str) -> Self {
         Self {
+            id: "Apache-2.0".to_owned(),
             value: LICENSE_APACHE2
                 .replace("%[AUTHOR]", author)
                 .replace("%[YEAR]", &Utc::now().year().to_string())
         }
     }
 
+    /// Creates a license with custom text
+    /// * text - the license text
+    pub fn custom<S>(text: S) -> Self
+    where S: Into<String> {
+        Self {
+            id: "SEE LICENSE IN LICENSE.md".to_owned(),
+            value: text.into(),
+        }
+    }
+
+    /// Get the license SPDX identifier for package manifest (example: "MIT")
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the license text
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
     /// Writes LICENSE file to "%DIR/LICENSE.md"
     /// * dir - the package root directory
     pub fn write_to<P>(&self, dir: P) -> Result<()>
     where P: Into<PathBuf> {
-        let dir = dir.into();
-        let path = dir.join("LICENSE.md");
+        self.write_into(&mut crate::FsSink::new(dir))
+    }
 
-        Ok(fs::write(path, &self.value).map_err(Error::from)?)
+    /// Writes LICENSE file "LICENSE.md" to output
+    /// * sink - the package output
+    pub fn write_into(&self, sink: &mut dyn OutputSink) -> Result<()> {
+        sink.write_file(Path::new("LICENSE.md"), self.value.as_bytes())
     }
 }
@@ -0,0 +1,171 @@
+use crate::{ Snippet, Snippets };
+
+/// The package lint kind
+/// * DuplicateName - the several snippets have the same name
+/// * DuplicatePrefix - the several snippets have the same prefix
+/// * ShadowedPrefix - the snippet prefix is a strict prefix of the other snippet prefix
+/// * IdenticalBody - the several snippets have the same body
+/// * UnknownVariable - the snippet body uses the variable which isn't documented (it's inserted as its name)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    DuplicateName,
+    DuplicatePrefix,
+    ShadowedPrefix,
+    IdenticalBody,
+    UnknownVariable,
+}
+
+/// The snippet location in package
+/// * group - the snippets group name
+/// * key - the snippet key in the snippets file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetRef {
+    pub group: String,
+    pub key: String,
+}
+
+impl std::fmt::Display for SnippetRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.group, self.key)
+    }
+}
+
+/// The package lint diagnostic
+/// * kind - the lint kind
+/// * language - the language scope where the problem was found ("*" for the snippets shown in all languages)
+/// * value - the clashing value (the name, prefix or body) or the unknown variable name
+/// * snippets - the involved snippets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub kind: LintKind,
+    pub language: String,
+    pub value: String,
+    pub snippets: Vec<SnippetRef>,
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let snippets = self.snippets.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ");
+        let lang = &self.language;
+
+        match self.kind {
+            LintKind::DuplicateName => write!(f, "[{lang}] duplicate name '{}': {snippets}", self.value),
+            LintKind::DuplicatePrefix => write!(f, "[{lang}] duplicate prefix '{}': {snippets}", self.value),
+            LintKind::ShadowedPrefix => write!(f, "[{lang}] prefix '{}' shadows the longer prefix: {snippets}", self.value),
+            LintKind::IdenticalBody => write!(f, "[{lang}] identical body: {snippets}"),
+            LintKind::UnknownVariable => write!(f, "[{lang}] unknown variable '{}': {snippets}", self.value),
+        }
+    }
+}
+
+impl Lint {
+    /// Analyzes the snippets groups && returns the found problems (per language where VS Code shows the snippets)
+    /// * groups - the package snippets groups
+    pub fn check(groups: &[Snippets]) -> Vec<Self> {
+        // collecting the snippets per language, where <(LANGUAGE, [(SNIPPET_REF, SNIPPET)])>
+        let mut scopes: Vec<(String, Vec<(SnippetRef, &Snippet)>)> = vec![];
+        let mut globals: Vec<(SnippetRef, &Snippet)> = vec![];
+        for group in groups {
+            for (key, snippet) in group.iter() {
+                let langs = group.snippet_languages(snippet);
+                let sref = SnippetRef { group: group.name.clone(), key: key.clone() };
+                if langs.is_empty() {
+                    globals.push((sref, snippet));
+                    continue;
+                }
+
+                for lang in langs {
+                    match scopes.iter_mut().find(|(l, _)| *l == lang) {
+                        Some((_, list)) => list.push((sref.clone(), snippet)),
+                        None => scopes.push((lang, vec![(sref.clone(), snippet)])),
+                    }
+                }
+            }
+        }
+
+        // the snippets without language are shown in all languages (their own problems are reported once as "*"):
+        let global_refs = globals.iter().map(|(sref, _)| sref.clone()).collect::<Vec<_>>();
+        if !globals.is_empty() {
+            for (_, list) in &mut scopes {
+                list.extend(globals.iter().cloned());
+            }
+            scopes.push(("*".into(), globals));
+        }
+
+        let mut lints = vec![];
+        for (lang, snippets) in &scopes {
+            let mut add = |kind, value: &str, refs: Vec<SnippetRef>| {
+                if lang == "*" || !refs.iter().all(|r| global_refs.contains(r)) {
+                    lints.push(Self { kind, language: lang.clone(), value: value.to_owned(), snippets: refs });
+                }
+            };
+
+            // duplicate names:
+            for (name, refs) in group_by(snippets, |s| vec![s.name.clone()]) {
+                add(LintKind::DuplicateName, &name, refs);
+            }
+
+            // duplicate prefixes:
+            for (prefix, refs) in group_by(snippets, |s| s.prefixes.clone()) {
+                add(LintKind::DuplicatePrefix, &prefix, refs);
+            }
+
+            // shadowed prefixes:
+            for (sref, snippet) in snippets {
+                for prefix in &snippet.prefixes {
+                    let shadowed = snippets
+                        .iter()
+                        .filter(|(other, s)| other != sref && s.prefixes.iter().any(|p| p.len() > prefix.len() && p.starts_with(prefix.as_str())))
+                        .map(|(other, _)| other.clone())
+                        .collect::<Vec<_>>();
+
+                    if !shadowed.is_empty() {
+                        add(LintKind::ShadowedPrefix, prefix, [vec![sref.clone()], shadowed].concat());
+                    }
+                }
+            }
+
+            // identical bodies:
+            for (body, refs) in group_by(snippets, |s| vec![s.body.join("\n")]) {
+                add(LintKind::IdenticalBody, &body, refs);
+            }
+        }
+
+        // unknown variables (once per snippet, the invalid bodies are reported by validation):
+        for group in groups {
+            for (key, snippet) in group.iter() {
+                let langs = group.snippet_languages(snippet);
+                for name in snippet.unknown_variables().unwrap_or_default() {
+                    lints.push(Self {
+                        kind: LintKind::UnknownVariable,
+                        language: if langs.is_empty() { "*".into() } else { langs.join(",") },
+                        value: name,
+                        snippets: vec![SnippetRef { group: group.name.clone(), key: key.clone() }],
+                    });
+                }
+            }
+        }
+
+        lints
+    }
+}
+
+/// Groups the snippets by the values && returns only the groups with several snippets, where <(VALUE, SNIPPET_REFS)>
+/// * snippets - the snippets list
+/// * values - the values getter
+fn group_by<F>(snippets: &[(SnippetRef, &Snippet)], values: F) -> Vec<(String, Vec<SnippetRef>)>
+where F: Fn(&Snippet) -> Vec<String>
+{
+    let mut groups: Vec<(String, Vec<SnippetRef>)> = vec![];
+    for (sref, snippet) in snippets {
+        for value in values(snippet) {
+            match groups.iter_mut().find(|(v, _)| *v == value) {
+                Some((_, refs)) => if !refs.contains(sref) { refs.push(sref.clone()) },
+                None => groups.push((value, vec![sref.clone()])),
+            }
+        }
+    }
+
+    groups.retain(|(_, refs)| refs.len() > 1);
+    groups
+}
@@ -1,10 +1,30 @@
-pub mod version;        pub use version::Version;
+pub mod version;        pub use version::{ Version, VersionError };
+pub mod version_req;    pub use version_req::{ VersionReq, VersionOp };
 pub mod category;       pub use category::Category;
 pub mod repository;     pub use repository::Repository;
 pub mod engines;        pub use engines::Engines;
-pub mod contributes;    pub use contributes::{ Contributes, SnippetsContribute };
+pub mod contributes;    pub use contributes::{ Contributes, SnippetsContribute, ThemeContribute, GrammarContribute, LanguageContribute };
+pub mod person;         pub use person::Person;
+pub mod bugs;           pub use bugs::Bugs;
+pub mod gallery_banner; pub use gallery_banner::{ GalleryBanner, BannerTheme };
+pub mod pricing;        pub use pricing::Pricing;
+pub mod badge;          pub use badge::Badge;
+pub mod qna;            pub use qna::Qna;
+pub mod sponsor;        pub use sponsor::Sponsor;
 
 pub mod readme;         pub use readme::Readme;
 pub mod license;        pub use license::License;
+pub mod on_enter_rule;  pub use on_enter_rule::{ OnEnterRule, EnterAction, IndentAction };
+pub mod language_config; pub use language_config::{ LanguageConfiguration, Comments, AutoClosingPair, ConfigRegex, Folding, FoldingMarkers, IndentationRules };
+pub mod language;       pub use language::LanguageDefinition;
+pub mod keybinding;     pub use keybinding::{ Keybinding, KeyChord, KeybindingConflict, Platform };
+pub mod keybindings_doc; pub use keybindings_doc::KeybindingsDoc;
+pub mod setting;        pub use setting::{ Setting, SettingType, SettingScope };
+pub mod configuration;  pub use configuration::{ Configuration, SettingsModel };
+pub mod settings_doc;   pub use settings_doc::SettingsDoc;
+pub mod lint;           pub use lint::{ Lint, LintKind, SnippetRef };
+pub mod vsix;           pub use vsix::{ Vsix, VsixFile, VsixManifest };
+pub mod diagnostic;     pub use diagnostic::{ Diagnostic, DiagnosticKind, Severity };
 
+#[allow(clippy::module_inception)]
 pub mod package;        pub use package::Package;
@@ -0,0 +1,92 @@
+use super::ConfigRegex;
+use serde::{ Serialize, Deserialize };
+
+/// The indentation action on Enter press
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndentAction {
+    #[default]
+    None,
+    Indent,
+    IndentOutdent,
+    Outdent,
+}
+
+/// The action on Enter press
+/// * indent - the indentation action
+/// * append_text - the text appended after the new line (example: " * ")
+/// * remove_text - the count of characters removed from the new line indentation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnterAction {
+    pub indent: IndentAction,
+    #[serde(rename = "appendText", skip_serializing_if = "Option::is_none", default)]
+    pub append_text: Option<String>,
+    #[serde(rename = "removeText", skip_serializing_if = "Option::is_none", default)]
+    pub remove_text: Option<usize>,
+}
+
+/// The rule applied on Enter press
+/// * before_text - the regex of text before cursor
+/// * after_text - the regex of text after cursor
+/// * previous_line_text - the regex of previous line text
+/// * action - the [action](EnterAction) applied if all regexes match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnEnterRule {
+    #[serde(rename = "beforeText")]
+    pub before_text: ConfigRegex,
+    #[serde(rename = "afterText", skip_serializing_if = "Option::is_none", default)]
+    pub after_text: Option<ConfigRegex>,
+    #[serde(rename = "previousLineText", skip_serializing_if = "Option::is_none", default)]
+    pub previous_line_text: Option<ConfigRegex>,
+    pub action: EnterAction,
+}
+
+impl OnEnterRule {
+    /// Creates a new Enter rule
+    /// * before_text - the regex of text before cursor (example: r"^\s*/\*\*(?!/)([^\*]|\*(?!/))*$")
+    /// * indent - the indentation action
+    pub fn new<S>(before_text: S, indent: IndentAction) -> Self
+    where S: Into<ConfigRegex> {
+        Self {
+            before_text: before_text.into(),
+            after_text: None,
+            previous_line_text: None,
+            action: EnterAction { indent, ..EnterAction::default() },
+        }
+    }
+
+    /// Sets the regex of text after cursor && returns 'Self'
+    pub fn after_text<S>(mut self, regex: S) -> Self
+    where S: Into<ConfigRegex> {
+        self.after_text = Some(regex.into());
+        self
+    }
+
+    /// Sets the regex of previous line text && returns 'Self'
+    pub fn previous_line_text<S>(mut self, regex: S) -> Self
+    where S: Into<ConfigRegex> {
+        self.previous_line_text = Some(regex.into());
+        self
+    }
+
+    /// Sets the text appended after the new line && returns 'Self'
+    pub fn append_text<S>(mut self, text: S) -> Self
+    where S: Into<String> {
+        self.action.append_text = Some(text.into());
+        self
+    }
+
+    /// Sets the count of characters removed from the new line indentation && returns 'Self'
+    pub fn remove_text(mut self, count: usize) -> Self {
+        self.action.remove_text = Some(count);
+        self
+    }
+
+    /// Get the rule regexes, where <(JSON_KEY, REGEX)>
+    pub fn regexes(&self) -> Vec<(&'static str, &ConfigRegex)> {
+        [("beforeText", Some(&self.before_text)), ("afterText", self.after_text.as_ref()), ("previousLineText", self.previous_line_text.as_ref())]
+            .into_iter()
+            .filter_map(|(key, regex)| regex.map(|r| (key, r)))
+            .collect()
+    }
+}
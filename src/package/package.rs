@@ -1,72 +1,695 @@
-use crate::{ prelude::*, Snippets };
+use crate::{ prelude::*, Snippets, SkippedSnippet, ColorTheme, ThemeKind, Grammar, OutputSink, FsSink, MemorySink, DryRunSink, PlannedWrite };
 use super::*;
-use serde::Serialize;
-use std::{ path::PathBuf, fs };
+use serde::{ Serialize, Deserialize };
+use std::{ collections::BTreeMap, path::{ Component, Path, PathBuf }, fs };
 
 /// The package generator
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Package {
     #[serde(rename = "name")]
     id: String,
-    #[serde(rename = "displayName")]
+    #[serde(rename = "displayName", default)]
     name: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    publisher: Option<String>,
+    #[serde(default)]
     description: String,
     version: Version,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    keywords: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    author: Option<Person>,
+    #[serde(rename = "license", skip_serializing_if = "Option::is_none", default)]
+    license_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    homepage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    bugs: Option<Bugs>,
+    #[serde(default)]
     categories: Vec<Category>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     icon: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     repository: Option<Repository>,
+    #[serde(rename = "galleryBanner", skip_serializing_if = "Option::is_none", default)]
+    gallery_banner: Option<GalleryBanner>,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    preview: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pricing: Option<Pricing>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    badges: Vec<Badge>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    qna: Option<Qna>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    sponsor: Option<Sponsor>,
     engines: Engines,
+    #[serde(default)]
     contributes: Contributes,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
 
-    #[serde(skip_serializing)]
-    snippets: Option<Vec<Snippets>>,
+    #[serde(skip)]
+    snippets: Vec<Snippets>,
 
-    #[serde(skip_serializing)]
-    license: License,
+    #[serde(skip)]
+    themes: Vec<ColorTheme>,
+
+    #[serde(skip)]
+    grammars: Vec<Grammar>,
+
+    #[serde(skip)]
+    languages: Vec<LanguageDefinition>,
+
+    #[serde(skip)]
+    license: Option<License>,
+
+    #[serde(skip)]
+    assets: Vec<(PathBuf, Vec<u8>)>,
 }
 
 impl Package {
+    /// The maximal description length (the longer descriptions are truncated on marketplace cards)
+    pub const MAX_DESCRIPTION_LEN: usize = 200;
+
     /// Creates a new snippets package object
     /// * id - the id name of the package
     /// * name - the package name
-    /// * title - the package title
     /// * description - the package description
     /// * version - the package version
     /// * icon - the package icon image
     /// * repository - the package repository URL
+    /// * snippets - the snippets groups
     /// * license - the package license
+    #[allow(clippy::too_many_arguments)]
     pub fn snippets<S, R>(id: S, name: S, description: S, version: Version, icon: S, repository: Option<R>, snippets: Vec<Snippets>, license: License) -> Self
     where S: Into<String>, R: Into<Repository>
     {
-        let mut this = Self {
+        let mut this = Self::new(id, name, description, version, icon, repository, license);
+        for snips in snippets {
+            this.add_snippets_group(snips)
+        }
+
+        this
+    }
+
+    /// Creates a new color themes package object
+    /// * id - the id name of the package
+    /// * name - the package name
+    /// * description - the package description
+    /// * version - the package version
+    /// * icon - the package icon image
+    /// * repository - the package repository URL
+    /// * themes - the color themes
+    /// * license - the package license
+    #[allow(clippy::too_many_arguments)]
+    pub fn theme<S, R>(id: S, name: S, description: S, version: Version, icon: S, repository: Option<R>, themes: Vec<ColorTheme>, license: License) -> Self
+    where S: Into<String>, R: Into<Repository>
+    {
+        let mut this = Self::new(id, name, description, version, icon, repository, license);
+        for theme in themes {
+            this.add_theme(theme)
+        }
+
+        this
+    }
+
+    /// Creates a new package object without contributions (the keybindings, settings && other contributions are added later)
+    /// * id - the id name of the package
+    /// * name - the package name
+    /// * description - the package description
+    /// * version - the package version
+    /// * icon - the package icon image
+    /// * repository - the package repository URL
+    /// * license - the package license
+    pub fn new<S, R>(id: S, name: S, description: S, version: Version, icon: S, repository: Option<R>, license: License) -> Self
+    where S: Into<String>, R: Into<Repository>
+    {
+        Self {
             id: id.into(),
             name: name.into(),
+            publisher: None,
             description: description.into(),
             version,
-            categories: vec![Category::Snippets],
+            keywords: vec![],
+            author: None,
+            license_id: Some(license.id().to_owned()),
+            homepage: None,
+            bugs: None,
+            categories: vec![],
             icon: Some(icon.into()),
             repository: repository.map(|v| v.into()),
+            gallery_banner: None,
+            preview: false,
+            pricing: None,
+            badges: vec![],
+            qna: None,
+            sponsor: None,
             engines: Engines::default(),
             contributes: Contributes::new(),
-            snippets: Some(vec![]),
-            license,
+            extra: serde_json::Map::new(),
+            snippets: vec![],
+            themes: vec![],
+            grammars: vec![],
+            languages: vec![],
+            license: Some(license),
+            assets: vec![],
+        }
+    }
+
+    /// Loads the package from existing extension directory (reads "package.json", the contributed snippets, theme, grammar && language files, LICENSE && icon),
+    /// returns the package && the list of skipped snippets which could not be converted
+    /// * dir - the extension root directory
+    pub fn load<P>(dir: P) -> Result<(Self, Vec<SkippedSnippet>)>
+    where P: AsRef<Path>
+    {
+        let dir = dir.as_ref();
+        Self::load_with(|path| fs::read(dir.join(path)).map_err(Error::from))
+    }
+
+    /// Loads the package from extension files (reads "package.json", the contributed snippets, theme, grammar && language files, LICENSE && icon),
+    /// returns the package && the list of skipped snippets which could not be converted
+    /// * read - the file reader (the path is relative to the extension root)
+    pub(crate) fn load_with<F>(read: F) -> Result<(Self, Vec<SkippedSnippet>)>
+    where F: Fn(&Path) -> Result<Vec<u8>>
+    {
+        let read_text = |path: &Path| -> Result<String> {
+            String::from_utf8(read(path)?).map_err(|_| Error::InvalidManifest(format!("the file '{}' isn't UTF-8 text", path.display())))
         };
 
-        for snips in snippets {
-            this.add_snippets_group(snips)
+        // reading the package manifest:
+        let json = read_text(Path::new("package.json"))?;
+        let mut this: Self = serde_json::from_str(&strip_json_comments(&json))
+            .map_err(|e| Error::InvalidManifest(e.to_string()))?;
+        if this.name.is_empty() {
+            this.name = this.id.clone();
         }
 
-        this
+        // collecting the languages of each snippets file:
+        let mut files: Vec<(PathBuf, Vec<String>)> = vec![];
+        for contrib in std::mem::take(&mut this.contributes.snippets) {
+            let path = contrib.path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>();
+            let langs = contrib.language.into_iter().collect::<Vec<_>>();
+            match files.iter_mut().find(|(p, _)| *p == path) {
+                Some((_, list)) => list.extend(langs),
+                None => files.push((path, langs)),
+            }
+        }
+
+        // reading the snippets groups:
+        let mut skipped = vec![];
+        for (path, langs) in files {
+            let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let (snips, skips) = Snippets::from_json(langs.join(","), name, String::new(), &read_text(&path)?)?;
+            skipped.extend(skips.into_iter().map(|skip| SkippedSnippet { file: path.clone(), ..skip }));
+            let snips = match path.file_name() {
+                Some(file_name) => snips.set_file_name(file_name),
+                None => snips,
+            };
+            this.add_snippets_group(snips);
+        }
+
+        // reading the color themes (the contributes are kept as is, the theme type && name come from them if the file omits these):
+        for contrib in &this.contributes.themes {
+            let path = contrib.path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>();
+            let mut theme = ColorTheme::from_json(&read_text(&path)?)?;
+            if let Some(kind) = ThemeKind::from_ui_theme(&contrib.ui_theme) {
+                theme.kind = kind;
+            }
+            if theme.name.is_empty() {
+                theme.name = contrib.label.clone();
+            }
+            this.themes.push(match path.file_name() {
+                Some(file_name) => theme.set_file_name(file_name),
+                None => theme,
+            });
+        }
+
+        // reading the grammars (the contributes are kept as is):
+        for contrib in &this.contributes.grammars {
+            let path = contrib.path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>();
+            let mut grammar = Grammar::from_json(contrib.language.as_deref(), &read_text(&path)?)?;
+            for (scope, lang) in &contrib.embedded_languages {
+                grammar = grammar.embed_language(scope.as_str(), lang.as_str());
+            }
+            for scope in &contrib.inject_to {
+                grammar = grammar.inject_to(scope.as_str());
+            }
+            this.grammars.push(match path.file_name() {
+                Some(file_name) => grammar.set_file_name(file_name),
+                None => grammar,
+            });
+        }
+
+        // reading the languages && their configurations (the contributes are kept as is):
+        for contrib in &this.contributes.languages {
+            let config = match &contrib.configuration {
+                Some(path) => {
+                    let path = path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>();
+                    LanguageConfiguration::from_json(&read_text(&path)?)?
+                }
+                None => LanguageConfiguration::new(),
+            };
+            this.languages.push(LanguageDefinition::from_contribute(contrib, config));
+        }
+
+        // reading the license:
+        this.license = ["LICENSE.md", "LICENSE", "LICENSE.txt"]
+            .iter()
+            .find_map(|name| read_text(Path::new(name)).ok())
+            .map(License::custom);
+
+        // reading the icon:
+        if let Some(icon) = this.icon.clone() {
+            if let Ok(data) = read(Path::new(&icon)) {
+                this.assets.push((PathBuf::from(icon), data));
+            }
+        }
+
+        Ok((this, skipped))
+    }
+
+    /// Get the package id name
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the package display name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the package publisher id
+    pub fn publisher(&self) -> Option<&str> {
+        self.publisher.as_deref()
+    }
+
+    /// Get the package description
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Get the package version
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Get the package keywords
+    pub fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+
+    /// Get the package author
+    pub fn author(&self) -> Option<&Person> {
+        self.author.as_ref()
+    }
+
+    /// Get the package license SPDX identifier (example: "MIT")
+    pub fn license_id(&self) -> Option<&str> {
+        self.license_id.as_deref()
+    }
+
+    /// Get the package homepage URL
+    pub fn homepage(&self) -> Option<&str> {
+        self.homepage.as_deref()
+    }
+
+    /// Get the package issue tracker
+    pub fn bugs(&self) -> Option<&Bugs> {
+        self.bugs.as_ref()
+    }
+
+    /// Get the package categories
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Get the package icon path
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    /// Get the package repository
+    pub fn repository(&self) -> Option<&Repository> {
+        self.repository.as_ref()
+    }
+
+    /// Get the marketplace page banner
+    pub fn gallery_banner(&self) -> Option<&GalleryBanner> {
+        self.gallery_banner.as_ref()
+    }
+
+    /// Check the package is marked as preview
+    pub fn preview(&self) -> bool {
+        self.preview
+    }
+
+    /// Get the package pricing model
+    pub fn pricing(&self) -> Option<Pricing> {
+        self.pricing
+    }
+
+    /// Get the marketplace page badges
+    pub fn badges(&self) -> &[Badge] {
+        &self.badges
+    }
+
+    /// Get the marketplace Q & A link
+    pub fn qna(&self) -> Option<&Qna> {
+        self.qna.as_ref()
+    }
+
+    /// Get the package sponsor link
+    pub fn sponsor(&self) -> Option<&Sponsor> {
+        self.sponsor.as_ref()
+    }
+
+    /// Get the package engines
+    pub fn engines(&self) -> &Engines {
+        &self.engines
+    }
+
+    /// Get the package contributes
+    pub fn contributes(&self) -> &Contributes {
+        &self.contributes
+    }
+
+    /// Get the other manifest fields which are kept as is (example: "main", "scripts", "activationEvents")
+    pub fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    /// Get the package snippets groups
+    pub fn snippets_groups(&self) -> &[Snippets] {
+        &self.snippets
+    }
+
+    /// Get the package color themes
+    pub fn themes(&self) -> &[ColorTheme] {
+        &self.themes
+    }
+
+    /// Get the package grammars
+    pub fn grammars(&self) -> &[Grammar] {
+        &self.grammars
+    }
+
+    /// Get the package languages
+    pub fn languages(&self) -> &[LanguageDefinition] {
+        &self.languages
+    }
+
+    /// Get the package license
+    pub fn license(&self) -> Option<&License> {
+        self.license.as_ref()
+    }
+
+    /// Sets a new package version && returns 'Self'
+    /// * version - the package version
+    pub fn set_version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets a new package description && returns 'Self'
+    /// * descr - the package description
+    pub fn set_descr<S>(mut self, descr: S) -> Self
+    where S: Into<String> {
+        self.description = descr.into();
+        self
     }
 
-    /// Adds a new snippets group to package
+    /// Sets a new package license && returns 'Self' (the manifest license identifier is updated too)
+    /// * license - the package license
+    pub fn set_license(mut self, license: License) -> Self {
+        self.license_id = Some(license.id().to_owned());
+        self.license = Some(license);
+        self
+    }
+
+    /// Sets the package publisher id && returns 'Self' (required by marketplace)
+    /// * publisher - the publisher id
+    pub fn set_publisher<S>(mut self, publisher: S) -> Self
+    where S: Into<String> {
+        self.publisher = Some(publisher.into());
+        self
+    }
+
+    /// Sets the package keywords && returns 'Self'
+    /// * keywords - the marketplace search keywords
+    pub fn set_keywords<S>(mut self, keywords: Vec<S>) -> Self
+    where S: Into<String> {
+        self.keywords = keywords.into_iter().map(|v| v.into()).collect();
+        self
+    }
+
+    /// Adds a package keyword && returns 'Self'
+    /// * keyword - the marketplace search keyword
+    pub fn add_keyword<S>(mut self, keyword: S) -> Self
+    where S: Into<String> {
+        self.keywords.push(keyword.into());
+        self
+    }
+
+    /// Sets the package author && returns 'Self'
+    /// * author - the package author (example: "John Doe <john@example.com>")
+    pub fn set_author<P>(mut self, author: P) -> Self
+    where P: Into<Person> {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Sets the package license SPDX identifier && returns 'Self'
+    /// * id - the license identifier (example: "MIT")
+    pub fn set_license_id<S>(mut self, id: S) -> Self
+    where S: Into<String> {
+        self.license_id = Some(id.into());
+        self
+    }
+
+    /// Sets the package homepage && returns 'Self'
+    /// * url - the homepage URL
+    pub fn set_homepage<S>(mut self, url: S) -> Self
+    where S: Into<String> {
+        self.homepage = Some(url.into());
+        self
+    }
+
+    /// Sets the package issue tracker && returns 'Self'
+    /// * bugs - the issue tracker (example: "https://github.com/user/repo/issues")
+    pub fn set_bugs<B>(mut self, bugs: B) -> Self
+    where B: Into<Bugs> {
+        self.bugs = Some(bugs.into());
+        self
+    }
+
+    /// Sets the marketplace page banner && returns 'Self'
+    /// * banner - the page banner
+    pub fn set_gallery_banner(mut self, banner: GalleryBanner) -> Self {
+        self.gallery_banner = Some(banner);
+        self
+    }
+
+    /// Marks the package as preview && returns 'Self'
+    /// * preview - the preview flag
+    pub fn set_preview(mut self, preview: bool) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Sets the package pricing model && returns 'Self'
+    /// * pricing - the pricing model
+    pub fn set_pricing(mut self, pricing: Pricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    /// Adds a marketplace page badge && returns 'Self'
+    /// * badge - the page badge
+    pub fn add_badge(mut self, badge: Badge) -> Self {
+        self.badges.push(badge);
+        self
+    }
+
+    /// Sets the marketplace Q & A link && returns 'Self'
+    /// * qna - the Q & A link
+    pub fn set_qna(mut self, qna: Qna) -> Self {
+        self.qna = Some(qna);
+        self
+    }
+
+    /// Sets the package sponsor link && returns 'Self'
+    /// * sponsor - the sponsorship page (example: "https://github.com/sponsors/user")
+    pub fn set_sponsor<S>(mut self, sponsor: S) -> Self
+    where S: Into<Sponsor> {
+        self.sponsor = Some(sponsor.into());
+        self
+    }
+
+    /// Sets the other manifest field && returns 'Self' (example: "main", "activationEvents")
+    /// * key - the manifest field name
+    /// * value - the field value
+    pub fn set_extra<S, V>(mut self, key: S, value: V) -> Self
+    where S: Into<String>, V: Into<serde_json::Value> {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds a package category && returns 'Self' (the categories of contributions are added automatically)
+    /// * category - the marketplace category
+    pub fn add_category(mut self, category: Category) -> Self {
+        self.reg_category(category);
+        self
+    }
+
+    /// Registrates the package category (if it's not registered yet)
+    fn reg_category(&mut self, category: Category) {
+        if !self.categories.contains(&category) {
+            self.categories.push(category);
+        }
+    }
+
+    /// Adds a new snippets group to package (the snippets file is contributed for each [contributed language](Snippets::contributed_languages))
     pub fn add_snippets_group(&mut self, snips: Snippets) {
-        if let Some(snippets) = self.snippets.as_mut() {
-            self.contributes.reg_snippets(SnippetsContribute::new(snips.language.clone(), &snips.file_name.clone()));
-            snippets.push(snips);
+        self.reg_category(Category::Snippets);
+
+        let langs = snips.contributed_languages();
+        if langs.is_empty() {
+            self.contributes.reg_snippets(SnippetsContribute::new(None::<String>, snips.file_name.clone()));
+        }
+        for lang in langs {
+            self.contributes.reg_snippets(SnippetsContribute::new(Some(lang), snips.file_name.clone()));
+        }
+        self.snippets.push(snips);
+    }
+
+    /// Adds a new color theme to package (the theme file is contributed with the theme name as label)
+    pub fn add_theme(&mut self, theme: ColorTheme) {
+        self.reg_category(Category::Themes);
+        self.contributes.reg_theme(ThemeContribute::new(theme.name.as_str(), theme.kind.ui_theme(), theme.file_name.clone()));
+        self.themes.push(theme);
+    }
+
+    /// Adds a new TextMate grammar to package (the grammar file is contributed with its language, embedded languages && injection targets)
+    pub fn add_grammar(&mut self, grammar: Grammar) {
+        self.reg_category(Category::ProgrammingLanguages);
+
+        let mut contrib = GrammarContribute::new(grammar.language.as_deref(), &grammar.scope_name, grammar.file_name.clone());
+        contrib.embedded_languages = grammar.embedded_languages().iter().cloned().collect();
+        contrib.inject_to = grammar.injected_to().to_vec();
+        self.contributes.reg_grammar(contrib);
+
+        self.grammars.push(grammar);
+    }
+
+    /// Adds a new language to package (the language && its configuration file are contributed)
+    pub fn add_language(&mut self, lang: LanguageDefinition) {
+        self.reg_category(Category::ProgrammingLanguages);
+        self.contributes.reg_language(lang.contribute());
+        self.languages.push(lang);
+    }
+
+    /// Adds a new keybinding to package
+    pub fn add_keybinding(&mut self, kb: Keybinding) {
+        self.reg_category(Category::Keymaps);
+        self.contributes.reg_keybinding(kb);
+    }
+
+    /// Adds a new settings section to package
+    pub fn add_configuration(&mut self, config: Configuration) {
+        self.contributes.reg_configuration(config);
+    }
+
+    /// Analyzes the package snippets: duplicate names, duplicate prefixes, shadowed prefixes && identical bodies per language
+    pub fn lint(&self) -> Vec<Lint> {
+        Lint::check(self.snippets_groups())
+    }
+
+    /// Checks the package by marketplace publishing rules (like 'vsce package' does),
+    /// the snippets [lints](Package::lint) are reported as warnings too
+    /// * dir - the package output directory (the icon, README, LICENSE && contributed files are searched there)
+    pub fn validate<P>(&self, dir: P) -> Vec<Diagnostic>
+    where P: AsRef<Path>
+    {
+        let dir = dir.as_ref();
+        let mut diags = vec![];
+        let mut add = |kind, value: &str| diags.push(Diagnostic::new(kind, value));
+
+        // the package id && publisher:
+        let is_name = |s: &str| s.starts_with(|c: char| c.is_ascii_alphanumeric()) && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !is_name(&self.id) || self.id.chars().any(|c| c.is_ascii_uppercase()) {
+            add(DiagnosticKind::InvalidId, &self.id);
+        }
+        match &self.publisher {
+            None => add(DiagnosticKind::MissingPublisher, ""),
+            Some(publisher) if !is_name(publisher) => add(DiagnosticKind::InvalidPublisher, publisher),
+            _ => {}
+        }
+
+        // the version && engines:
+        if self.version.is_pre_release() {
+            add(DiagnosticKind::PreReleaseVersion, &self.version.to_string());
+        }
+        if self.engines.vscode.op == VersionOp::Any {
+            add(DiagnosticKind::AnyEngine, &self.engines.vscode.to_string());
         }
+
+        // the description:
+        if self.description.trim().is_empty() {
+            add(DiagnosticKind::EmptyDescription, "");
+        } else if self.description.chars().count() > Self::MAX_DESCRIPTION_LEN {
+            add(DiagnosticKind::LongDescription, &Self::MAX_DESCRIPTION_LEN.to_string());
+        }
+
+        // the icon:
+        if let Some(icon) = &self.icon {
+            let ext = Path::new(icon).extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+            if ext == "svg" {
+                add(DiagnosticKind::SvgIcon, icon);
+            } else if ext != "png" {
+                add(DiagnosticKind::NonPngIcon, icon);
+            }
+            if !dir.join(icon).is_file() {
+                add(DiagnosticKind::MissingIcon, icon);
+            }
+        }
+
+        // the repository:
+        if let Some(repo) = self.repository.as_ref().filter(|repo| !repo.url.starts_with("https://")) {
+            add(DiagnosticKind::InsecureRepository, &repo.url);
+        }
+
+        // the README images (the relative links are resolved by the repository URL):
+        let readme = fs::read_to_string(dir.join("README.md")).unwrap_or_default();
+        let re = Regex::new(r#"!\[[^\]]*\]\(\s*<?([^)\s>]+)|<img\s[^>]*src\s*=\s*["']([^"']+)"#).unwrap();
+        for caps in re.captures_iter(&readme) {
+            let src = caps.get(1).or(caps.get(2)).map(|m| m.as_str()).unwrap_or_default();
+            if src.starts_with("http://") {
+                add(DiagnosticKind::InsecureReadmeImage, src);
+            } else if self.repository.is_none() && !["https://", "data:"].iter().any(|scheme| src.starts_with(scheme)) {
+                add(DiagnosticKind::RelativeReadmeImage, src);
+            }
+        }
+
+        // the contributed files && license:
+        for path in self.contributes.paths() {
+            if !dir.join(path).is_file() {
+                add(DiagnosticKind::MissingContribution, &path.to_string_lossy());
+            }
+        }
+        if !["LICENSE.md", "LICENSE", "LICENSE.txt"].iter().any(|name| dir.join(name).is_file()) {
+            add(DiagnosticKind::MissingLicense, "");
+        }
+
+        // the keybindings:
+        for conflict in Keybinding::conflicts(&self.contributes.keybindings) {
+            add(DiagnosticKind::KeybindingConflict, &conflict.to_string());
+        }
+
+        // the snippets:
+        diags.extend(self.lint().into_iter().map(Diagnostic::from));
+
+        diags
     }
 
     /// Converting to JSON string
@@ -74,37 +697,118 @@ impl Package {
         serde_json::to_string_pretty(&self).map_err(Error::from)
     }
     
-    /// Writes the package to directory path
-    pub fn write_to<P>(&self, dir: P) -> Result<()>
-    where P: Into<PathBuf>
-    {
-        // preparing directory path:
-        let dir = dir.into();
-        fs::create_dir_all(&dir).map_err(Error::from)?;
-        let pkg_path = dir.clone().join("package.json");
-        // dbg!(&pkg_path);    // DEBUG: The package manifest path
+    /// Adds the extra package file && returns 'Self' (example: the icon image)
+    /// * path - the file path relative to the package root (example: "images/icon.png")
+    /// * data - the file contents
+    pub fn add_asset<P>(mut self, path: P, data: Vec<u8>) -> Self
+    where P: Into<PathBuf> {
+        self.assets.push((path.into(), data));
+        self
+    }
+
+    /// Get the extra package files, where <(PATH, CONTENTS)>
+    pub fn assets(&self) -> &[(PathBuf, Vec<u8>)] {
+        &self.assets
+    }
+
+    /// Writes the package files to output (the contributions are checked before writing anything)
+    /// * sink - the package output (example: [FsSink], [MemorySink], [DryRunSink])
+    pub fn write_into(&self, sink: &mut dyn OutputSink) -> Result<()> {
+        // checking the contributions before writing anything:
+        for snips in self.snippets_groups() {
+            snips.validate()?;
+        }
+        for theme in &self.themes {
+            theme.validate()?;
+        }
+        for grammar in &self.grammars {
+            grammar.validate()?;
+        }
+        for lang in &self.languages {
+            lang.validate()?;
+        }
+        for kb in &self.contributes.keybindings {
+            kb.validate()?;
+        }
+        for config in &self.contributes.configuration {
+            config.validate()?;
+        }
 
-        // generating a README file:
-        let mut readme = Readme::new(&self.name, &self.description);
-        
         // writing package manifest file "package.json":
-        let json_cnts = self.to_json()?;
-        fs::write(pkg_path, json_cnts).map_err(Error::from)?;
-        
-        // writing snippets files:
-        if let Some(snippets) = &self.snippets {
-            for snips in snippets {
-                let doc = snips.write_to(&dir)?;
-                readme.add_line(&doc.to_string());
-            }
+        sink.write_file(Path::new("package.json"), self.to_json()?.as_bytes())?;
+
+        // writing snippets files, theme files && README (with keybindings && settings tables):
+        let mut readme = Readme::new(&self.name, &self.description);
+        for snips in self.snippets_groups() {
+            let doc = snips.write_into(sink)?;
+            readme.add_line(&doc.to_string());
+        }
+        for theme in &self.themes {
+            let doc = theme.write_into(sink)?;
+            readme.add_line(&doc.to_string());
+        }
+        if !self.contributes.keybindings.is_empty() {
+            readme.add_line(&KeybindingsDoc::from_keybindings(&self.contributes.keybindings).to_string());
         }
+        for config in &self.contributes.configuration {
+            readme.add_line(&SettingsDoc::from_configuration(config).to_string());
+        }
+        readme.write_into(sink)?;
 
-        // writing the README file:
-        readme.write_to(dir.clone())?;
+        // writing grammar && language configuration files:
+        for grammar in &self.grammars {
+            grammar.write_into(sink)?;
+        }
+        for lang in &self.languages {
+            lang.write_into(sink)?;
+        }
 
         // writing LICENSE file:
-        self.license.write_to(dir)?;
+        if let Some(license) = &self.license {
+            license.write_into(sink)?;
+        }
+
+        // writing the extra files:
+        for (path, data) in &self.assets {
+            sink.write_file(path, data)?;
+        }
 
         Ok(())
     }
+
+    /// Renders the package files in memory, where <(PATH, CONTENTS)>
+    pub fn render(&self) -> Result<BTreeMap<PathBuf, Vec<u8>>> {
+        let mut sink = MemorySink::new();
+        self.write_into(&mut sink)?;
+        Ok(sink.files)
+    }
+
+    /// Lists what would be written to directory path (nothing is written)
+    /// * dir - the package output directory
+    pub fn dry_run<P>(&self, dir: P) -> Result<Vec<PlannedWrite>>
+    where P: Into<PathBuf>
+    {
+        let mut sink = DryRunSink::new(dir);
+        self.write_into(&mut sink)?;
+        Ok(sink.planned)
+    }
+
+    /// Writes the package to directory path
+    pub fn write_to<P>(&self, dir: P) -> Result<()>
+    where P: Into<PathBuf>
+    {
+        self.write_into(&mut FsSink::new(dir))
+    }
+
+    /// Writes the installable VS Code extension archive (the publisher && the icon [asset](Package::add_asset) are required)
+    /// * path - the archive file path (example: "rust-snippets-1.0.0.vsix")
+    pub fn write_vsix<P>(&self, path: P) -> Result<()>
+    where P: AsRef<Path>
+    {
+        let data = vsix::pack(self)?;
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, data).map_err(Error::from)
+    }
 }
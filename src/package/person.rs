@@ -0,0 +1,78 @@
+use serde::{ Serialize, Deserialize };
+
+/// The package author (or contributor) info
+/// * name - the person name
+/// * email - the person email
+/// * url - the person web page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Person {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Person {
+    /// Creates a new person info
+    /// * name - the person name
+    pub fn new<S>(name: S) -> Self
+    where S: Into<String> {
+        Self {
+            name: name.into(),
+            email: None,
+            url: None,
+        }
+    }
+
+    /// Sets the person email && returns 'Self'
+    pub fn set_email<S>(mut self, email: S) -> Self
+    where S: Into<String> {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Sets the person web page && returns 'Self'
+    pub fn set_url<S>(mut self, url: S) -> Self
+    where S: Into<String> {
+        self.url = Some(url.into());
+        self
+    }
+}
+
+impl From<&str> for Person {
+    /// Parses the NPM person string "Name <email> (url)" (the email && url are optional)
+    fn from(s: &str) -> Self {
+        let between = |open: char, close: char| {
+            let start = s.find(open)? + 1;
+            let end = start + s[start..].find(close)?;
+            Some(s[start..end].trim().to_owned())
+        };
+        let name_end = s.find(['<', '(']).unwrap_or(s.len());
+
+        Self {
+            name: s[..name_end].trim().to_owned(),
+            email: between('<', '>'),
+            url: between('(', ')'),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Person {
+    /// Reads the person from "Name <email> (url)" string or '{ "name": ..., "email": ..., "url": ... }' object
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Short(String),
+            Full { name: String, email: Option<String>, url: Option<String> },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Short(s) => Self::from(s.as_str()),
+            Repr::Full { name, email, url } => Self { name, email, url },
+        })
+    }
+}
@@ -0,0 +1,18 @@
+use serde::{ Serialize, Deserialize };
+
+/// The package pricing model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pricing {
+    Free,
+    Trial,
+}
+
+impl Pricing {
+    /// Get the pricing model name (example: "Free")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Free => "Free",
+            Self::Trial => "Trial",
+        }
+    }
+}
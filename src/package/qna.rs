@@ -0,0 +1,46 @@
+use serde::{ Serialize, Deserialize };
+
+/// The marketplace Q & A link
+/// * Marketplace - the default marketplace Q & A page
+/// * Disabled - the Q & A link is hidden
+/// * Url - the custom Q & A page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Qna {
+    Marketplace,
+    Disabled,
+    Url(String),
+}
+
+impl Serialize for Qna {
+    /// Writes the Q & A as "marketplace", false or the URL string
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: serde::Serializer
+    {
+        match self {
+            Self::Marketplace => serializer.serialize_str("marketplace"),
+            Self::Disabled => serializer.serialize_bool(false),
+            Self::Url(url) => serializer.serialize_str(url),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Qna {
+    /// Reads the Q & A from "marketplace", false or the URL string
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Flag(bool),
+            Url(String),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Flag(false) => Self::Disabled,
+            Repr::Flag(true) => Self::Marketplace,
+            Repr::Url(url) if url == "marketplace" => Self::Marketplace,
+            Repr::Url(url) => Self::Url(url),
+        })
+    }
+}
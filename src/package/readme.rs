@@ -1,5 +1,6 @@
 use crate::prelude::*;
-use std::{ path::PathBuf, fs };
+use crate::OutputSink;
+use std::path::{ Path, PathBuf };
 
 /// The README file generator
 #[derive(Debug, Clone)]
@@ -26,9 +27,12 @@ impl Readme {
     /// * dir - the package root directory
     pub fn write_to<P>(&self, dir: P) -> Result<()>
     where P: Into<PathBuf> {
-        let dir = dir.into();
-        let path = dir.join("README.md");
+        self.write_into(&mut crate::FsSink::new(dir))
+    }
 
-        Ok(fs::write(path, &self.value).map_err(Error::from)?)
+    /// Writes README file "README.md" to output
+    /// * sink - the package output
+    pub fn write_into(&self, sink: &mut dyn OutputSink) -> Result<()> {
+        sink.write_file(Path::new("README.md"), self.value.as_bytes())
     }
 }
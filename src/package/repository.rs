@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{ Serialize, Deserialize };
 
 /// The repository info
 /// * type - the repository type (example: git)
@@ -17,3 +17,22 @@ impl From<&str> for Repository {
         }
     }
 }
+
+impl<'de> Deserialize<'de> for Repository {
+    /// Reads the repository from URL string or '{ "type": ..., "url": ... }' object
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Url(String),
+            Full { r#type: String, url: String },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Url(url) => Self::from(url.as_str()),
+            Repr::Full { r#type, url } => Self { r#type, url },
+        })
+    }
+}
@@ -0,0 +1,273 @@
+use serde::{ Serialize, Deserialize };
+use serde_json::Value;
+
+/// The setting value type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    Array,
+    Object,
+    Null,
+}
+
+impl SettingType {
+    /// Get the type of JSON value (the integer numbers are 'Integer')
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::String(_) => Self::String,
+            Value::Number(n) if n.is_i64() || n.is_u64() => Self::Integer,
+            Value::Number(_) => Self::Number,
+            Value::Bool(_) => Self::Boolean,
+            Value::Array(_) => Self::Array,
+            Value::Object(_) => Self::Object,
+            Value::Null => Self::Null,
+        }
+    }
+
+    /// Check the JSON value has this type (the integer is accepted as number)
+    pub fn accepts(&self, value: &Value) -> bool {
+        let kind = Self::of(value);
+        kind == *self || (*self == Self::Number && kind == Self::Integer)
+    }
+
+    /// Get the type name (example: "boolean")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Integer => "integer",
+            Self::Boolean => "boolean",
+            Self::Array => "array",
+            Self::Object => "object",
+            Self::Null => "null",
+        }
+    }
+}
+
+impl std::fmt::Display for SettingType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+
+/// The setting scope (where the setting could be configured)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SettingScope {
+    Application,
+    Machine,
+    MachineOverridable,
+    Window,
+    Resource,
+    LanguageOverridable,
+}
+
+
+/// The extension setting (the 'contributes.configuration.properties' entry)
+/// * types - the value [types](SettingType) (any value is allowed if empty)
+/// * default - the default value
+/// * description - the plain text description
+/// * markdown_description - the markdown description (is shown instead of plain description)
+/// * enum_values - the allowed values
+/// * enum_descriptions - the allowed values descriptions
+/// * scope - the setting [scope](SettingScope)
+/// * deprecation_message - the message shown if the setting is deprecated
+/// * order - the setting position in the settings editor
+/// * extra - the other JSON schema fields which are kept as is (example: "items", "minimum", "pattern")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Setting {
+    #[serde(rename = "type", serialize_with = "ser_types", deserialize_with = "de_types", skip_serializing_if = "Vec::is_empty", default)]
+    pub types: Vec<SettingType>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub description: Option<String>,
+    #[serde(rename = "markdownDescription", skip_serializing_if = "Option::is_none", default)]
+    pub markdown_description: Option<String>,
+    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty", default)]
+    pub enum_values: Vec<Value>,
+    #[serde(rename = "enumDescriptions", skip_serializing_if = "Vec::is_empty", default)]
+    pub enum_descriptions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub scope: Option<SettingScope>,
+    #[serde(rename = "deprecationMessage", skip_serializing_if = "Option::is_none", default)]
+    pub deprecation_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub order: Option<i64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+impl Setting {
+    /// Creates a new setting without default value
+    /// * kind - the value type
+    pub fn new(kind: SettingType) -> Self {
+        Self {
+            types: vec![kind],
+            default: None,
+            description: None,
+            markdown_description: None,
+            enum_values: vec![],
+            enum_descriptions: vec![],
+            scope: None,
+            deprecation_message: None,
+            order: None,
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    /// Creates a new setting from the default value (the type is taken from value, the null isn't stored as default)
+    ///
+    /// The null default (the 'None' value) gives the type 'null', so the real type must be set explicitly
+    /// * default - the default value (example: `json!(true)`)
+    pub fn with_default<V>(default: V) -> Self
+    where V: Into<Value> {
+        let default = default.into();
+        Self::new(SettingType::of(&default)).set_default(default)
+    }
+
+    /// Sets the value type && returns 'Self' (the other types are removed)
+    pub fn set_type(mut self, kind: SettingType) -> Self {
+        self.types = vec![kind];
+        self
+    }
+
+    /// Adds the allowed value type && returns 'Self'
+    /// * kind - the additional type (example: 'SettingType::Null' for optional value)
+    pub fn add_type(mut self, kind: SettingType) -> Self {
+        if !self.types.contains(&kind) {
+            self.types.push(kind);
+        }
+        self
+    }
+
+    /// Check the JSON value has one of the setting types (any value is accepted if the types are empty)
+    pub fn accepts(&self, value: &Value) -> bool {
+        self.types.is_empty() || self.types.iter().any(|kind| kind.accepts(value))
+    }
+
+    /// Get the types names separated by " or " (example: "string or null"), the empty types are "any"
+    pub fn types_str(&self) -> String {
+        match self.types.is_empty() {
+            true => "any".into(),
+            false => self.types.iter().map(SettingType::as_str).collect::<Vec<_>>().join(" or "),
+        }
+    }
+
+    /// Sets the default value && returns 'Self' (the null resets the default value)
+    pub fn set_default<V>(mut self, default: V) -> Self
+    where V: Into<Value> {
+        self.default = Some(default.into()).filter(|v| !v.is_null());
+        self
+    }
+
+    /// Sets the plain text description && returns 'Self'
+    pub fn set_descr<S>(mut self, descr: S) -> Self
+    where S: Into<String> {
+        self.description = Some(descr.into());
+        self
+    }
+
+    /// Sets the markdown description && returns 'Self'
+    pub fn set_markdown_descr<S>(mut self, descr: S) -> Self
+    where S: Into<String> {
+        self.markdown_description = Some(descr.into());
+        self
+    }
+
+    /// Adds the allowed value && returns 'Self'
+    /// * value - the allowed value (example: "auto")
+    /// * descr - the value description
+    pub fn add_enum<V, S>(mut self, value: V, descr: S) -> Self
+    where V: Into<Value>, S: Into<String> {
+        self.enum_values.push(value.into());
+        self.enum_descriptions.push(descr.into());
+        self
+    }
+
+    /// Sets the setting scope && returns 'Self'
+    pub fn set_scope(mut self, scope: SettingScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Marks the setting as deprecated && returns 'Self'
+    /// * message - the deprecation message (example: "Use 'myExt.format.enable' instead")
+    pub fn set_deprecated<S>(mut self, message: S) -> Self
+    where S: Into<String> {
+        self.deprecation_message = Some(message.into());
+        self
+    }
+
+    /// Sets the setting position in the settings editor && returns 'Self'
+    pub fn set_order(mut self, order: i64) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Sets the other JSON schema field && returns 'Self'
+    /// * key - the schema field name (example: "minimum")
+    /// * value - the field value
+    pub fn set_extra<S, V>(mut self, key: S, value: V) -> Self
+    where S: Into<String>, V: Into<Value> {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+
+    /// Checks the default value && the allowed values match the setting type, returns the error message
+    pub fn check(&self) -> std::result::Result<(), String> {
+        if self.types == [SettingType::Null] {
+            return Err("the type is 'null' (the type of 'None' default must be set explicitly)".into());
+        }
+        if let Some(value) = self.enum_values.iter().find(|v| !self.accepts(v)) {
+            return Err(format!("the enum value {value} isn't {}", self.types_str()));
+        }
+        if !self.enum_descriptions.is_empty() && self.enum_descriptions.len() != self.enum_values.len() {
+            return Err(format!("the enum has {} values, but {} descriptions", self.enum_values.len(), self.enum_descriptions.len()));
+        }
+
+        if let Some(default) = &self.default {
+            if !self.accepts(default) {
+                return Err(format!("the default value {default} isn't {}", self.types_str()));
+            }
+            if !self.enum_values.is_empty() && !self.enum_values.contains(default) {
+                return Err(format!("the default value {default} isn't in enum"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+/// Writes the setting types as string (if single) or array
+fn ser_types<S>(types: &[SettingType], serializer: S) -> Result<S::Ok, S::Error>
+where S: serde::Serializer
+{
+    match types {
+        [single] => single.serialize(serializer),
+        _ => types.serialize(serializer),
+    }
+}
+
+/// Reads the setting types from string or array
+fn de_types<'de, D>(deserializer: D) -> Result<Vec<SettingType>, D::Error>
+where D: serde::Deserializer<'de>
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        One(SettingType),
+        Many(Vec<SettingType>),
+    }
+
+    Ok(match Repr::deserialize(deserializer)? {
+        Repr::One(kind) => vec![kind],
+        Repr::Many(types) => types,
+    })
+}
@@ -0,0 +1,67 @@
+use crate::tools::markdown_table;
+use super::Configuration;
+
+/// The settings documentation generator
+/// * title - the settings section title
+/// * body - the documentation body, where '<(KEY, TYPE, DEFAULT, DESCRIPTION)>'
+#[derive(Debug, Clone)]
+pub struct SettingsDoc {
+    pub title: String,
+    pub body: Vec<(String, String, String, String)>,
+}
+
+impl SettingsDoc {
+    /// Creates a new 'SettingsDoc' object
+    /// * title - the settings section title
+    pub fn new<S>(title: S) -> Self
+    where S: Into<String> {
+        Self { title: title.into(), body: vec![] }
+    }
+
+    /// Generates the documentation of settings section
+    /// (the markdown description is preferred, the allowed values && deprecation are appended, the untitled section is named "Settings")
+    /// * config - the settings section
+    pub fn from_configuration(config: &Configuration) -> Self {
+        let title = if config.title.is_empty() { "Settings" } else { config.title.as_str() };
+        let mut doc = Self::new(title);
+
+        for (key, setting) in config.settings() {
+            let mut descr = setting.markdown_description.clone()
+                .or(setting.description.clone())
+                .unwrap_or_default()
+                .replace('\n', " ");
+
+            if !setting.enum_values.is_empty() {
+                let values = setting.enum_values.iter().map(|v| format!("`{v}`")).collect::<Vec<_>>();
+                descr = format!("{descr} (one of: {})", values.join(", ")).trim_start().to_owned();
+            }
+            if let Some(message) = &setting.deprecation_message {
+                descr = format!("**Deprecated:** {message} {descr}").trim_end().to_owned();
+            }
+
+            let default = setting.default.as_ref().map(|v| format!("`{v}`")).unwrap_or_default();
+            doc.body.push((format!("`{key}`"), setting.types_str(), default, descr));
+        }
+
+        doc
+    }
+}
+
+impl std::fmt::Display for SettingsDoc {
+    /// Converts the documentation object to string
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let rows = self.body
+            .iter()
+            .map(|(key, kind, default, descr)| vec![key, kind, default, descr])
+            .collect::<Vec<_>>();
+
+        write!(f, "# {} [settings]:\n{}", self.title, markdown_table(&["Setting:", "Type:", "Default:", "Description:"], &rows))
+    }
+}
+
+impl From<SettingsDoc> for String {
+    /// Converts the documentation object to string
+    fn from(doc: SettingsDoc) -> Self {
+        doc.to_string()
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{ Serialize, Deserialize };
+
+/// The package sponsor link
+/// * url - the sponsorship page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sponsor {
+    pub url: String,
+}
+
+impl From<&str> for Sponsor {
+    fn from(s: &str) -> Self {
+        Self {
+            url: s.to_owned(),
+        }
+    }
+}
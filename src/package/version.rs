@@ -1,41 +1,149 @@
-use crate::prelude::*;
+use std::cmp::Ordering;
 
-/// The package version parser
-#[derive(Debug, Clone)]
+/// The semantic version `MAJOR.MINOR.PATCH[-PRE_RELEASE][+BUILD]` (see semver.org)
+/// * major - the major version (incompatible changes)
+/// * minor - the minor version (compatible features)
+/// * patch - the patch version (compatible fixes)
+/// * pre - the pre-release identifiers (example: "beta.1")
+/// * build - the build metadata (example: "20240709"), it doesn't affect the version precedence
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Version {
-    value: String,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: String,
+    pub build: String,
 }
 
 impl Version {
-    /// Get as string slice
-    pub fn as_str(&self) -> &str {
-        &self.value
+    /// Creates a new release version
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch, pre: String::new(), build: String::new() }
     }
 
-    /// Converts to string
-    pub fn to_string(&self) -> String {
-        self.value.clone()
+    /// Check the version is a pre-release
+    pub fn is_pre_release(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Get the next major version (example: "1.2.3" -> "2.0.0", "2.0.0-beta" -> "2.0.0")
+    pub fn bump_major(&self) -> Self {
+        if self.is_pre_release() && self.minor == 0 && self.patch == 0 {
+            Self::new(self.major, 0, 0)
+        } else {
+            Self::new(self.major + 1, 0, 0)
+        }
+    }
+
+    /// Get the next minor version (example: "1.2.3" -> "1.3.0", "1.3.0-beta" -> "1.3.0")
+    pub fn bump_minor(&self) -> Self {
+        if self.is_pre_release() && self.patch == 0 {
+            Self::new(self.major, self.minor, 0)
+        } else {
+            Self::new(self.major, self.minor + 1, 0)
+        }
+    }
+
+    /// Get the next patch version (example: "1.2.3" -> "1.2.4", "1.2.4-beta" -> "1.2.4")
+    pub fn bump_patch(&self) -> Self {
+        if self.is_pre_release() {
+            Self::new(self.major, self.minor, self.patch)
+        } else {
+            Self::new(self.major, self.minor, self.patch + 1)
+        }
+    }
+
+    /// Sets the pre-release identifiers && returns 'Self'
+    /// * pre - the pre-release identifiers (example: "rc.1")
+    pub fn set_pre(mut self, pre: &str) -> std::result::Result<Self, VersionError> {
+        check_identifiers(pre, pre, true)?;
+        self.pre = pre.to_owned();
+        Ok(self)
+    }
+
+    /// Sets the build metadata && returns 'Self'
+    /// * build - the build metadata (example: "20240709")
+    pub fn set_build(mut self, build: &str) -> std::result::Result<Self, VersionError> {
+        check_identifiers(build, build, false)?;
+        self.build = build.to_owned();
+        Ok(self)
     }
 }
 
 impl std::str::FromStr for Version {
-    type Err = Error;
-    
+    type Err = VersionError;
+
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let re = Regex::new(r"^\^?\d+(\.\d+)*$").unwrap();
-        if re.is_match(s) {
-            Ok(Self {
-                value: s.to_owned(),
-            })
-        } else {
-            Err(Error::IncorrectVersionFormat)
+        let error = |reason: &str| VersionError::new(s, reason);
+
+        let (rest, build) = s.split_once('+').unwrap_or((s, ""));
+        let (core, pre) = rest.split_once('-').unwrap_or((rest, ""));
+
+        let parts = core.split('.').collect::<Vec<_>>();
+        if parts.len() != 3 {
+            return Err(error("expected the format 'MAJOR.MINOR.PATCH'"));
+        }
+
+        let mut numbers = [0u64; 3];
+        for (number, part) in numbers.iter_mut().zip(&parts) {
+            *number = parse_number(s, part)?;
+        }
+
+        if rest.contains('-') && pre.is_empty() {
+            return Err(error("the pre-release is empty"));
         }
+        if s.contains('+') && build.is_empty() {
+            return Err(error("the build metadata is empty"));
+        }
+        if !pre.is_empty() { check_identifiers(s, pre, true)?; }
+        if !build.is_empty() { check_identifiers(s, build, false)?; }
+
+        Ok(Self {
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers[2],
+            pre: pre.to_owned(),
+            build: build.to_owned(),
+        })
     }
 }
 
 impl std::fmt::Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.value)
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() { write!(f, "-{}", self.pre)?; }
+        if !self.build.is_empty() { write!(f, "+{}", self.build)?; }
+        Ok(())
+    }
+}
+
+impl Ord for Version {
+    /// Compares the versions by semver precedence (the build metadata is compared last to keep the order total)
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => cmp_identifiers(&self.pre, &other.pre),
+            })
+            .then_with(|| cmp_identifiers(&self.build, &other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -43,6 +151,86 @@ impl serde::Serialize for Version {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where S: serde::Serializer
     {
-        serializer.serialize_str(&self.value)
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+
+/// The version (or version requirement) parse error
+/// * value - the incorrect value
+/// * reason - the error description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionError {
+    pub value: String,
+    pub reason: String,
+}
+
+impl VersionError {
+    pub(crate) fn new(value: &str, reason: &str) -> Self {
+        Self { value: value.to_owned(), reason: reason.to_owned() }
+    }
+}
+
+impl std::fmt::Display for VersionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "incorrect version '{}': {}", self.value, self.reason)
+    }
+}
+
+impl std::error::Error for VersionError {}
+
+
+/// Parses the version number (without leading zeros)
+/// * value - the whole version string (for errors)
+/// * part - the number string
+fn parse_number(value: &str, part: &str) -> std::result::Result<u64, VersionError> {
+    if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+        return Err(VersionError::new(value, &format!("the version number '{part}' isn't a number")));
+    }
+    if part.len() > 1 && part.starts_with('0') {
+        return Err(VersionError::new(value, &format!("the version number '{part}' has leading zeros")));
+    }
+    part.parse().map_err(|_| VersionError::new(value, &format!("the version number '{part}' is too large")))
+}
+
+/// Checks the dot-separated identifiers `[0-9A-Za-z-]+`
+/// * value - the whole version string (for errors)
+/// * ids - the identifiers
+/// * numeric_zeros - forbid the leading zeros in numeric identifiers (for pre-release)
+fn check_identifiers(value: &str, ids: &str, numeric_zeros: bool) -> std::result::Result<(), VersionError> {
+    for id in ids.split('.') {
+        if id.is_empty() {
+            return Err(VersionError::new(value, "the empty pre-release or build identifier"));
+        }
+        if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(VersionError::new(value, &format!("the identifier '{id}' has invalid symbols")));
+        }
+        if numeric_zeros && id.len() > 1 && id.starts_with('0') && id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(VersionError::new(value, &format!("the identifier '{id}' has leading zeros")));
+        }
+    }
+    Ok(())
+}
+
+/// Compares the dot-separated identifiers (the numeric ones are compared as numbers && are lower than alphanumeric ones)
+fn cmp_identifiers(a: &str, b: &str) -> Ordering {
+    let mut a_ids = a.split('.').filter(|s| !s.is_empty());
+    let mut b_ids = b.split('.').filter(|s| !s.is_empty());
+
+    loop {
+        match (a_ids.next(), b_ids.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => {
+                let ord = match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y).then_with(|| a.cmp(b)),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(b),
+                };
+                if ord != Ordering::Equal { return ord; }
+            }
+        }
     }
 }
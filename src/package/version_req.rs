@@ -0,0 +1,141 @@
+use super::{ Version, VersionError };
+
+/// The version requirement operator
+/// * Any - any version `*`
+/// * Exact - the exact version `=1.2.3` / `1.2.3`
+/// * Greater - the greater version `>1.2.3`
+/// * GreaterEq - the greater or equal version `>=1.2.3`
+/// * Less - the less version `<1.2.3`
+/// * LessEq - the less or equal version `<=1.2.3`
+/// * Tilde - the patch updates `~1.2.3` (>=1.2.3, <1.3.0)
+/// * Caret - the compatible updates `^1.2.3` (>=1.2.3, <2.0.0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionOp {
+    Any,
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+impl VersionOp {
+    /// Get the operator symbol (example: ">=")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Any => "*",
+            Self::Exact => "",
+            Self::Greater => ">",
+            Self::GreaterEq => ">=",
+            Self::Less => "<",
+            Self::LessEq => "<=",
+            Self::Tilde => "~",
+            Self::Caret => "^",
+        }
+    }
+}
+
+
+/// The version requirement (example: the `engines.vscode` range "^1.90.0")
+/// * op - the requirement operator
+/// * version - the required version (ignored by 'VersionOp::Any')
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    pub op: VersionOp,
+    pub version: Version,
+}
+
+impl VersionReq {
+    /// Creates a new version requirement
+    /// * op - the requirement operator
+    /// * version - the required version
+    pub fn new(op: VersionOp, version: Version) -> Self {
+        Self { op, version }
+    }
+
+    /// Creates the compatible updates requirement `^VERSION`
+    /// * version - the minimal version
+    pub fn caret(version: Version) -> Self {
+        Self::new(VersionOp::Caret, version)
+    }
+
+    /// Check the version matches the requirement
+    /// * version - the checked version
+    pub fn matches(&self, version: &Version) -> bool {
+        let req = &self.version;
+        let base = |v: &Version| (v.major, v.minor, v.patch);
+
+        match self.op {
+            VersionOp::Any => true,
+            VersionOp::Exact => version == req,
+            VersionOp::Greater => version > req,
+            VersionOp::GreaterEq => version >= req,
+            VersionOp::Less => version < req,
+            VersionOp::LessEq => version <= req,
+            VersionOp::Tilde => version >= req && base(version) < (req.major, req.minor + 1, 0),
+            VersionOp::Caret => {
+                let upper = match (req.major, req.minor) {
+                    (0, 0) => (0, 0, req.patch + 1),
+                    (0, minor) => (0, minor + 1, 0),
+                    (major, _) => (major + 1, 0, 0),
+                };
+                version >= req && base(version) < upper
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for VersionReq {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let value = s.trim();
+        if value == "*" {
+            return Ok(Self::new(VersionOp::Any, Version::new(0, 0, 0)));
+        }
+
+        let ops = [
+            (">=", VersionOp::GreaterEq), ("<=", VersionOp::LessEq), (">", VersionOp::Greater), ("<", VersionOp::Less),
+            ("~", VersionOp::Tilde), ("^", VersionOp::Caret), ("=", VersionOp::Exact),
+        ];
+        let (op, rest) = ops
+            .iter()
+            .find_map(|(prefix, op)| value.strip_prefix(prefix).map(|rest| (*op, rest)))
+            .unwrap_or((VersionOp::Exact, value));
+
+        let version = rest
+            .trim_start()
+            .parse()
+            .map_err(|e: VersionError| VersionError::new(s, &format!("the range version is incorrect: {}", e.reason)))?;
+
+        Ok(Self::new(op, version))
+    }
+}
+
+impl std::fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.op {
+            VersionOp::Any => write!(f, "*"),
+            op => write!(f, "{}{}", op.as_str(), self.version),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for VersionReq {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for VersionReq {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where S: serde::Serializer
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
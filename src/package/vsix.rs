@@ -0,0 +1,441 @@
+use crate::{ prelude::*, SkippedSnippet };
+use super::{ Package, Pricing, Qna };
+use std::{ io::{ Cursor, Read, Write }, path::{ Component, Path }, fs };
+use zip::{ ZipArchive, ZipWriter, CompressionMethod, write::SimpleFileOptions };
+
+/// The extension payload directory in archive
+const PAYLOAD_DIR: &str = "extension";
+
+/// The opened VSIX archive
+/// * package - the extension package (with snippets, themes, license && icon)
+/// * manifest - the 'extension.vsixmanifest' metadata
+/// * readme - the README text
+/// * files - the archive files listing
+/// * skipped - the snippets which could not be loaded into package
+#[derive(Debug, Clone)]
+pub struct Vsix {
+    pub package: Package,
+    pub manifest: VsixManifest,
+    pub readme: Option<String>,
+    pub files: Vec<VsixFile>,
+    pub skipped: Vec<SkippedSnippet>,
+}
+
+impl Vsix {
+    /// Reads the VSIX archive file
+    /// * path - the archive file path
+    pub fn read<P>(path: P) -> Result<Self>
+    where P: AsRef<Path>
+    {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Reads the VSIX archive from memory
+    /// * data - the archive contents
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut zip = ZipArchive::new(Cursor::new(data))?;
+
+        // the files listing && contents:
+        let mut files = vec![];
+        let mut contents = vec![];
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i)?;
+            if file.is_dir() { continue; }
+
+            let mut data = vec![];
+            file.read_to_end(&mut data)?;
+            files.push(VsixFile { path: file.name().to_owned(), size: file.size(), compressed_size: file.compressed_size() });
+            contents.push((file.name().to_owned(), data));
+        }
+        let find = |path: &str| contents.iter().find(|(name, _)| name == path).map(|(_, data)| data);
+
+        // the VSIX manifest:
+        let manifest = find("extension.vsixmanifest")
+            .ok_or_else(|| Error::InvalidManifest("the 'extension.vsixmanifest' file is not found in VSIX".into()))?;
+        let manifest = VsixManifest::parse(&String::from_utf8_lossy(manifest));
+
+        // the extension package:
+        let (package, skipped) = Package::load_with(|path| {
+            let name = payload_path(path);
+            find(&name)
+                .cloned()
+                .ok_or_else(|| Error::Io(std::io::Error::new(std::io::ErrorKind::NotFound, format!("the file '{name}' is not found in VSIX"))))
+        })?;
+
+        // the README (the details asset or the conventional name):
+        let readme = manifest
+            .asset("Microsoft.VisualStudio.Services.Content.Details")
+            .and_then(&find)
+            .or_else(|| find(&format!("{PAYLOAD_DIR}/README.md")))
+            .map(|data| String::from_utf8_lossy(data).to_string());
+
+        Ok(Self { package, manifest, readme, files, skipped })
+    }
+}
+
+
+/// The VSIX archive file info
+/// * path - the file path in archive (example: "extension/package.json")
+/// * size - the uncompressed file size
+/// * compressed_size - the compressed file size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VsixFile {
+    pub path: String,
+    pub size: u64,
+    pub compressed_size: u64,
+}
+
+
+/// The 'extension.vsixmanifest' metadata
+/// * id - the extension id
+/// * version - the extension version
+/// * publisher - the publisher id
+/// * display_name - the extension display name
+/// * description - the extension description
+/// * tags - the search tags
+/// * categories - the marketplace categories
+/// * properties - the gallery properties, where <(ID, VALUE)>
+/// * assets - the archive assets, where <(TYPE, PATH)>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VsixManifest {
+    pub id: String,
+    pub version: String,
+    pub publisher: String,
+    pub display_name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub categories: Vec<String>,
+    pub properties: Vec<(String, String)>,
+    pub assets: Vec<(String, String)>,
+}
+
+impl VsixManifest {
+    /// Parses the 'extension.vsixmanifest' XML (the unknown elements are ignored)
+    /// * xml - the manifest XML text
+    pub fn parse(xml: &str) -> Self {
+        let element = |name: &str| {
+            Regex::new(&format!(r"<{name}(?:\s[^>]*)?>([^<]*)</{name}>"))
+                .unwrap()
+                .captures(xml)
+                .map(|caps| unescape_xml(&caps[1]))
+                .unwrap_or_default()
+        };
+        let list = |name: &str| {
+            element(name)
+                .split(',')
+                .map(|s| s.trim().to_owned())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+        };
+        let tags = |name: &str, first: &str, second: &str| {
+            Regex::new(&format!(r"<{name}\s[^>]*>"))
+                .unwrap()
+                .find_iter(xml)
+                .filter_map(|tag| Some((attribute(tag.as_str(), first)?, attribute(tag.as_str(), second)?)))
+                .collect::<Vec<_>>()
+        };
+        let identity = Regex::new(r"<Identity\s[^>]*>").unwrap().find(xml).map(|m| m.as_str()).unwrap_or_default();
+
+        Self {
+            id: attribute(identity, "Id").unwrap_or_default(),
+            version: attribute(identity, "Version").unwrap_or_default(),
+            publisher: attribute(identity, "Publisher").unwrap_or_default(),
+            display_name: element("DisplayName"),
+            description: element("Description"),
+            tags: list("Tags"),
+            categories: list("Categories"),
+            properties: tags("Property", "Id", "Value"),
+            assets: tags("Asset", "Type", "Path"),
+        }
+    }
+
+    /// Get the gallery property value
+    /// * id - the property id (example: "Microsoft.VisualStudio.Code.Engine")
+    pub fn property(&self, id: &str) -> Option<&str> {
+        self.properties.iter().find(|(i, _)| i == id).map(|(_, v)| v.as_str())
+    }
+
+    /// Get the asset path in archive
+    /// * kind - the asset type (example: "Microsoft.VisualStudio.Code.Manifest")
+    pub fn asset(&self, kind: &str) -> Option<&str> {
+        self.assets.iter().find(|(t, _)| t == kind).map(|(_, p)| p.as_str())
+    }
+}
+
+/// Packs the package to VSIX archive (the OPC zip with '[Content_Types].xml', 'extension.vsixmanifest' && 'extension/' payload),
+/// the publisher && the icon asset (if the icon is set) are required
+/// * pkg - the package
+pub(crate) fn pack(pkg: &Package) -> Result<Vec<u8>> {
+    if pkg.publisher().is_none() {
+        return Err(Error::InvalidManifest("the publisher is required to build VSIX".into()));
+    }
+
+    let files = pkg.render()?
+        .into_iter()
+        .map(|(path, data)| (payload_path(&path), data))
+        .collect::<Vec<_>>();
+    let paths = files.iter().map(|(path, _)| path.as_str()).collect::<Vec<_>>();
+
+    // the icon is referenced by 'package.json', so it must be packed too:
+    if let Some(icon) = pkg.icon().filter(|icon| !paths.contains(&payload_path(Path::new(icon)).as_str())) {
+        return Err(Error::InvalidManifest(format!("the icon '{icon}' is not added to package (see 'Package::add_asset')")));
+    }
+
+    let mut zip = ZipWriter::new(Cursor::new(vec![]));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("extension.vsixmanifest", options)?;
+    zip.write_all(vsix_manifest(pkg, &paths).as_bytes())?;
+    zip.start_file("[Content_Types].xml", options)?;
+    zip.write_all(content_types(&paths).as_bytes())?;
+
+    for (path, data) in &files {
+        zip.start_file(path.as_str(), options)?;
+        zip.write_all(data)?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Generates the 'extension.vsixmanifest' file from package manifest fields
+/// * pkg - the package
+/// * paths - the payload file paths in archive
+fn vsix_manifest(pkg: &Package, paths: &[&str]) -> String {
+    let payload = |path: &str| payload_path(Path::new(path));
+    let has = |path: &str| paths.contains(&path);
+
+    // the search tags (keywords, snippets, languages && themes):
+    let mut tags = pkg.keywords().to_vec();
+    if !pkg.contributes().snippets.is_empty() {
+        tags.push("snippet".into());
+    }
+    let snippet_langs = pkg.contributes().snippets.iter().filter_map(|c| c.language.as_ref());
+    for lang in snippet_langs.chain(pkg.contributes().languages.iter().map(|c| &c.id)) {
+        if !tags.contains(lang) { tags.push(lang.clone()); }
+    }
+    if !pkg.contributes().themes.is_empty() {
+        tags.extend(["theme".into(), "color-theme".into()]);
+    }
+    let categories = pkg.categories().iter().map(|c| c.as_str()).collect::<Vec<_>>().join(",");
+    let flags = if pkg.preview() { "Public Preview" } else { "Public" };
+
+    // the properties:
+    let mut props: Vec<(&str, String)> = vec![
+        ("Microsoft.VisualStudio.Code.Engine", pkg.engines().vscode.to_string()),
+        ("Microsoft.VisualStudio.Code.ExtensionDependencies", extra_list(pkg, "extensionDependencies").join(",")),
+        ("Microsoft.VisualStudio.Code.ExtensionPack", extra_list(pkg, "extensionPack").join(",")),
+        ("Microsoft.VisualStudio.Code.ExtensionKind", extension_kind(pkg).join(",")),
+        ("Microsoft.VisualStudio.Code.LocalizedLanguages", String::new()),
+    ];
+    if let Some(repo) = pkg.repository() {
+        props.push(("Microsoft.VisualStudio.Services.Links.Source", repo.url.clone()));
+        props.push(("Microsoft.VisualStudio.Services.Links.Getstarted", repo.url.clone()));
+        if repo.url.contains("github.com") {
+            props.push(("Microsoft.VisualStudio.Services.Links.GitHub", repo.url.clone()));
+        } else {
+            props.push(("Microsoft.VisualStudio.Services.Links.Repository", repo.url.clone()));
+        }
+    }
+    if let Some(url) = pkg.bugs().and_then(|b| b.url.clone()) {
+        props.push(("Microsoft.VisualStudio.Services.Links.Support", url));
+    }
+    if let Some(url) = pkg.homepage() {
+        props.push(("Microsoft.VisualStudio.Services.Links.Learn", url.into()));
+    }
+    if let Some(banner) = pkg.gallery_banner() {
+        props.push(("Microsoft.VisualStudio.Services.Branding.Color", banner.color.clone()));
+        props.push(("Microsoft.VisualStudio.Services.Branding.Theme", banner.theme.as_str().into()));
+    }
+    props.push(("Microsoft.VisualStudio.Services.GitHubFlavoredMarkdown", "true".into()));
+    props.push(("Microsoft.VisualStudio.Services.Content.Pricing", pkg.pricing().unwrap_or(Pricing::Free).as_str().into()));
+    match pkg.qna() {
+        Some(Qna::Disabled) => props.push(("Microsoft.VisualStudio.Services.EnableMarketplaceQnA", "false".into())),
+        Some(Qna::Url(url)) => {
+            props.push(("Microsoft.VisualStudio.Services.EnableMarketplaceQnA", "true".into()));
+            props.push(("Microsoft.VisualStudio.Services.CustomerQnALink", url.clone()));
+        }
+        _ => {}
+    }
+    if let Some(sponsor) = pkg.sponsor() {
+        props.push(("Microsoft.VisualStudio.Code.SponsorLink", sponsor.url.clone()));
+    }
+
+    // the assets:
+    let mut assets = vec![("Microsoft.VisualStudio.Code.Manifest", payload("package.json"))];
+    let readme = payload("README.md");
+    if has(&readme) {
+        assets.push(("Microsoft.VisualStudio.Services.Content.Details", readme));
+    }
+    let license = payload("LICENSE.md");
+    let license = has(&license).then_some(license);
+    if let Some(license) = &license {
+        assets.push(("Microsoft.VisualStudio.Services.Content.License", license.clone()));
+    }
+    let icon = pkg.icon().map(payload);
+    if let Some(icon) = &icon {
+        assets.push(("Microsoft.VisualStudio.Services.Icons.Default", icon.clone()));
+    }
+
+    // generating XML:
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml += "<PackageManifest Version=\"2.0.0\" xmlns=\"http://schemas.microsoft.com/developer/vsx-schema/2011\" xmlns:d=\"http://schemas.microsoft.com/developer/vsx-schema-design/2011\">\n";
+    xml += "  <Metadata>\n";
+    xml += &format!(
+        "    <Identity Language=\"en-US\" Id=\"{}\" Version=\"{}\" Publisher=\"{}\" />\n",
+        escape_xml(pkg.id()), pkg.version(), escape_xml(pkg.publisher().unwrap_or_default()),
+    );
+    xml += &format!("    <DisplayName>{}</DisplayName>\n", escape_xml(pkg.name()));
+    xml += &format!("    <Description xml:space=\"preserve\">{}</Description>\n", escape_xml(pkg.description()));
+    xml += &format!("    <Tags>{}</Tags>\n", escape_xml(&tags.join(",")));
+    xml += &format!("    <Categories>{}</Categories>\n", escape_xml(&categories));
+    xml += &format!("    <GalleryFlags>{flags}</GalleryFlags>\n");
+    if !pkg.badges().is_empty() {
+        xml += "    <Badges>\n";
+        for badge in pkg.badges() {
+            xml += &format!(
+                "      <Badge Link=\"{}\" ImgUri=\"{}\" Description=\"{}\" />\n",
+                escape_xml(&badge.href), escape_xml(&badge.url), escape_xml(&badge.description),
+            );
+        }
+        xml += "    </Badges>\n";
+    }
+    xml += "    <Properties>\n";
+    for (id, value) in &props {
+        xml += &format!("      <Property Id=\"{id}\" Value=\"{}\" />\n", escape_xml(value));
+    }
+    xml += "    </Properties>\n";
+    if let Some(license) = &license {
+        xml += &format!("    <License>{}</License>\n", escape_xml(license));
+    }
+    if let Some(icon) = &icon {
+        xml += &format!("    <Icon>{}</Icon>\n", escape_xml(icon));
+    }
+    xml += "  </Metadata>\n";
+    xml += "  <Installation>\n    <InstallationTarget Id=\"Microsoft.VisualStudio.Code\" />\n  </Installation>\n";
+    xml += "  <Dependencies />\n";
+    xml += "  <Assets>\n";
+    for (kind, path) in &assets {
+        xml += &format!("    <Asset Type=\"{kind}\" Path=\"{}\" Addressable=\"true\" />\n", escape_xml(path));
+    }
+    xml += "  </Assets>\n";
+    xml += "</PackageManifest>\n";
+
+    xml
+}
+
+/// Get the extension kinds like 'vsce' does (the declarative extension without "main" && "browser" runs in UI, workspace && web)
+/// * pkg - the package
+fn extension_kind(pkg: &Package) -> Vec<String> {
+    let has = |key: &str| pkg.extra().get(key).is_some_and(|v| !v.is_null());
+    let deduced = match (has("main"), has("browser")) {
+        (true, true) => vec!["workspace", "web"],
+        (true, false) => vec!["workspace"],
+        (false, true) => vec!["web"],
+        _ if !extra_list(pkg, "extensionDependencies").is_empty() || !extra_list(pkg, "extensionPack").is_empty() => vec!["workspace", "web"],
+        _ => vec!["ui", "workspace", "web"],
+    };
+
+    // the manifest kinds (the web kind is added if the extension can run in web):
+    let mut kinds = match pkg.extra().get("extensionKind") {
+        Some(serde_json::Value::String(kind)) if kind == "ui" => vec!["ui".to_owned(), "workspace".to_owned()],
+        Some(serde_json::Value::String(kind)) => vec![kind.clone()],
+        Some(serde_json::Value::Array(_)) => extra_list(pkg, "extensionKind"),
+        _ => return deduced.into_iter().map(String::from).collect(),
+    };
+    if deduced.contains(&"web") && !kinds.iter().any(|k| k == "web") {
+        kinds.push("web".into());
+    }
+    kinds
+}
+
+/// Get the strings list of the other manifest field (example: "extensionDependencies")
+/// * pkg - the package
+/// * key - the manifest field name
+fn extra_list(pkg: &Package, key: &str) -> Vec<String> {
+    pkg.extra()
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|list| list.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+/// Generates the '[Content_Types].xml' file (the MIME type of each file extension in archive)
+/// * paths - the payload file paths in archive
+fn content_types(paths: &[&str]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml += "<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\n";
+    xml += "  <Default Extension=\".vsixmanifest\" ContentType=\"text/xml\" />\n";
+
+    let mut exts: Vec<String> = vec![];
+    for path in paths {
+        match Path::new(path).extension() {
+            Some(ext) => {
+                let ext = ext.to_string_lossy().to_lowercase();
+                if !exts.contains(&ext) {
+                    xml += &format!("  <Default Extension=\".{}\" ContentType=\"{}\" />\n", escape_xml(&ext), mime_type(&ext));
+                    exts.push(ext);
+                }
+            }
+            None => xml += &format!("  <Override PartName=\"/{}\" ContentType=\"application/octet-stream\" />\n", escape_xml(path)),
+        }
+    }
+
+    xml += "</Types>\n";
+    xml
+}
+
+/// Get the MIME type of file extension
+/// * ext - the lowercase file extension (example: "json")
+fn mime_type(ext: &str) -> &'static str {
+    match ext {
+        "json" | "code-snippets" => "application/json",
+        "md" => "text/markdown",
+        "txt" => "text/plain",
+        "xml" | "tmlanguage" => "text/xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "js" => "application/javascript",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Get the archive path of payload file
+/// * path - the file path relative to the extension root (example: "./snippets/rust.code-snippets")
+fn payload_path(path: &Path) -> String {
+    let parts = path
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    format!("{PAYLOAD_DIR}/{}", parts.join("/"))
+}
+
+/// Get the XML tag attribute value
+/// * tag - the XML tag text (example: `<Asset Type="..." Path="..." />`)
+/// * name - the attribute name
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    Regex::new(&format!(r#"\s{name}\s*=\s*"([^"]*)""#))
+        .unwrap()
+        .captures(tag)
+        .map(|caps| unescape_xml(&caps[1]))
+}
+
+/// Unescapes the XML special symbols
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Escapes the XML special symbols
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
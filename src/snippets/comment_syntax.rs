@@ -0,0 +1,78 @@
+/// The programming language comment syntax
+/// * line - the line comment token (example: "//")
+/// * block - the block comment start && end tokens (example: ("/*", "*/"))
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentSyntax {
+    pub line: Option<&'static str>,
+    pub block: Option<(&'static str, &'static str)>,
+}
+
+/// Creates the comment syntax (for registry)
+const fn syntax(line: Option<&'static str>, block: Option<(&'static str, &'static str)>) -> CommentSyntax {
+    CommentSyntax { line, block }
+}
+
+/// The comment syntax registry, where <(LANGUAGE_IDS, COMMENT_SYNTAX)>
+const REGISTRY: &[(&[&str], CommentSyntax)] = &[
+    (&[
+        "c", "cpp", "csharp", "cuda-cpp", "d", "dart", "go", "groovy", "java", "javascript", "javascriptreact",
+        "jsonc", "kotlin", "less", "objective-c", "objective-cpp", "php", "proto", "rust", "scala", "scss",
+        "swift", "typescript", "typescriptreact", "verilog",
+    ], syntax(Some("//"), Some(("/*", "*/")))),
+    (&["zig"], syntax(Some("//"), None)),
+    (&["fsharp"], syntax(Some("//"), Some(("(*", "*)")))),
+    (&["css"], syntax(None, Some(("/*", "*/")))),
+    (&["html", "xml", "xsl", "markdown", "vue-html", "svg"], syntax(None, Some(("<!--", "-->")))),
+    (&["handlebars"], syntax(None, Some(("{{!--", "--}}")))),
+    (&["razor"], syntax(None, Some(("@*", "*@")))),
+    (&[
+        "python", "shellscript", "perl", "r", "yaml", "toml", "dockerfile", "makefile", "elixir", "nim",
+        "graphql", "cmake", "properties", "gitignore", "ignore",
+    ], syntax(Some("#"), None)),
+    (&["ruby"], syntax(Some("#"), Some(("=begin", "=end")))),
+    (&["powershell"], syntax(Some("#"), Some(("<#", "#>")))),
+    (&["coffeescript"], syntax(Some("#"), Some(("###", "###")))),
+    (&["julia"], syntax(Some("#"), Some(("#=", "=#")))),
+    (&["sql"], syntax(Some("--"), Some(("/*", "*/")))),
+    (&["lua"], syntax(Some("--"), Some(("--[[", "]]")))),
+    (&["haskell", "elm"], syntax(Some("--"), Some(("{-", "-}")))),
+    (&["ada"], syntax(Some("--"), None)),
+    (&["latex", "tex", "bibtex", "erlang"], syntax(Some("%"), None)),
+    (&["matlab"], syntax(Some("%"), Some(("%{", "%}")))),
+    (&["clojure", "lisp", "scheme", "ini"], syntax(Some(";"), None)),
+    (&["vb"], syntax(Some("'"), None)),
+    (&["bat"], syntax(Some("REM"), None)),
+    (&["vim"], syntax(Some("\""), None)),
+    (&["ocaml"], syntax(None, Some(("(*", "*)")))),
+];
+
+impl CommentSyntax {
+    /// Get the comment syntax of the VS Code language id (returns 'None' if the language is unknown)
+    /// * lang - the language id (example: "rust")
+    pub fn of(lang: &str) -> Option<Self> {
+        REGISTRY
+            .iter()
+            .find(|(langs, _)| langs.contains(&lang))
+            .map(|(_, syntax)| *syntax)
+    }
+
+    /// Get the line comment token (or the block tokens if the language has no line comments),
+    /// where <(START_TOKEN, END_TOKEN)>
+    pub fn line_or_block(&self) -> (&'static str, Option<&'static str>) {
+        match (self.line, self.block) {
+            (Some(line), _) => (line, None),
+            (None, Some((start, end))) => (start, Some(end)),
+            (None, None) => ("", None),
+        }
+    }
+
+    /// Get the block comment tokens (or the line token if the language has no block comments),
+    /// where <(START_TOKEN, END_TOKEN)>
+    pub fn block_or_line(&self) -> (&'static str, Option<&'static str>) {
+        match (self.block, self.line) {
+            (Some((start, end)), _) => (start, Some(end)),
+            (None, Some(line)) => (line, None),
+            (None, None) => ("", None),
+        }
+    }
+}
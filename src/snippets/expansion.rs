@@ -0,0 +1,373 @@
+use crate::prelude::*;
+use super::{ Snippet, SnippetNode, SnippetTransform, SnippetVariable, VariableResolver, CommentSyntax };
+use chrono::{ Datelike, Timelike, NaiveDateTime };
+use std::{ ops::Range, path::PathBuf };
+
+/// The snippet expansion context (the editor state for variables resolving)
+/// * selected_text - the selected text
+/// * current_line - the current line text
+/// * current_word - the word under cursor
+/// * line_index - the zero-based line index
+/// * cursor_index - the zero-based cursor number (for multi-cursor insertion)
+/// * file_path - the current file path
+/// * workspace_folder - the opened workspace folder path
+/// * clipboard - the clipboard contents
+/// * date - the current date && time
+/// * timezone_offset - the timezone offset in minutes (example: 180 is "+03:00")
+/// * language - the current file language id (for comment variables)
+/// * seed - the seed of 'RANDOM', 'RANDOM_HEX' && 'UUID' values (the same seed gives the same values)
+#[derive(Debug, Clone, Default)]
+pub struct ExpansionContext {
+    pub selected_text: Option<String>,
+    pub current_line: Option<String>,
+    pub current_word: Option<String>,
+    pub line_index: usize,
+    pub cursor_index: usize,
+    pub file_path: Option<PathBuf>,
+    pub workspace_folder: Option<PathBuf>,
+    pub clipboard: Option<String>,
+    pub date: Option<NaiveDateTime>,
+    pub timezone_offset: i32,
+    pub language: Option<String>,
+    pub seed: u64,
+}
+
+impl ExpansionContext {
+    /// Creates a new empty expansion context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the selected text && returns 'Self'
+    pub fn set_selection<S>(mut self, text: S) -> Self
+    where S: Into<String> {
+        self.selected_text = Some(text.into());
+        self
+    }
+
+    /// Sets the current line text, its index && returns 'Self'
+    /// * index - the zero-based line index
+    /// * text - the line text
+    pub fn set_line<S>(mut self, index: usize, text: S) -> Self
+    where S: Into<String> {
+        self.line_index = index;
+        self.current_line = Some(text.into());
+        self
+    }
+
+    /// Sets the word under cursor && returns 'Self'
+    pub fn set_word<S>(mut self, word: S) -> Self
+    where S: Into<String> {
+        self.current_word = Some(word.into());
+        self
+    }
+
+    /// Sets the current file path && returns 'Self'
+    pub fn set_file<P>(mut self, path: P) -> Self
+    where P: Into<PathBuf> {
+        self.file_path = Some(path.into());
+        self
+    }
+
+    /// Sets the opened workspace folder && returns 'Self'
+    pub fn set_workspace<P>(mut self, path: P) -> Self
+    where P: Into<PathBuf> {
+        self.workspace_folder = Some(path.into());
+        self
+    }
+
+    /// Sets the clipboard contents && returns 'Self'
+    pub fn set_clipboard<S>(mut self, text: S) -> Self
+    where S: Into<String> {
+        self.clipboard = Some(text.into());
+        self
+    }
+
+    /// Sets the current date && time && returns 'Self'
+    pub fn set_date(mut self, date: NaiveDateTime) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// Sets the timezone offset && returns 'Self'
+    /// * minutes - the offset in minutes (example: -300 is "-05:00")
+    pub fn set_timezone(mut self, minutes: i32) -> Self {
+        self.timezone_offset = minutes;
+        self
+    }
+
+    /// Sets the current file language id && returns 'Self'
+    pub fn set_language<S>(mut self, lang: S) -> Self
+    where S: Into<String> {
+        self.language = Some(lang.into());
+        self
+    }
+
+    /// Sets the seed of random values && returns 'Self'
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Generates the random bytes from seed
+    fn random_bytes(&self) -> [u8; 16] {
+        md5::compute(self.seed.to_le_bytes()).0
+    }
+}
+
+impl VariableResolver for ExpansionContext {
+    fn resolve(&self, var: SnippetVariable) -> Option<String> {
+        use SnippetVariable::*;
+
+        let file = self.file_path.as_ref();
+        let workspace = self.workspace_folder.as_ref();
+        let date = self.date.as_ref();
+        let comments = self.language.as_deref().and_then(CommentSyntax::of);
+        let path_str = |p: &std::path::Path| p.to_string_lossy().to_string();
+
+        match var {
+            TmSelectedText => self.selected_text.clone(),
+            TmCurrentLine => self.current_line.clone(),
+            TmCurrentWord => self.current_word.clone(),
+            TmLineIndex => Some(self.line_index.to_string()),
+            TmLineNumber => Some((self.line_index + 1).to_string()),
+            TmFilename => file.and_then(|p| p.file_name()).map(|s| s.to_string_lossy().to_string()),
+            TmFilenameBase => file.and_then(|p| p.file_stem()).map(|s| s.to_string_lossy().to_string()),
+            TmDirectory => file.and_then(|p| p.parent()).map(path_str),
+            TmDirectoryBase => file.and_then(|p| p.parent()).and_then(|p| p.file_name()).map(|s| s.to_string_lossy().to_string()),
+            TmFilepath => file.map(|p| path_str(p)),
+            RelativeFilepath => file.map(|p| path_str(workspace.and_then(|w| p.strip_prefix(w).ok()).unwrap_or(p))),
+            Clipboard => self.clipboard.clone(),
+            WorkspaceName => workspace.and_then(|p| p.file_name()).map(|s| s.to_string_lossy().to_string()),
+            WorkspaceFolder => workspace.map(|p| path_str(p)),
+            CursorIndex => Some(self.cursor_index.to_string()),
+            CursorNumber => Some((self.cursor_index + 1).to_string()),
+            CurrentYear => date.map(|d| d.year().to_string()),
+            CurrentYearShort => date.map(|d| format!("{:02}", d.year() % 100)),
+            CurrentMonth => date.map(|d| format!("{:02}", d.month())),
+            CurrentMonthName => date.map(|d| d.format("%B").to_string()),
+            CurrentMonthNameShort => date.map(|d| d.format("%b").to_string()),
+            CurrentDate => date.map(|d| format!("{:02}", d.day())),
+            CurrentDayName => date.map(|d| d.format("%A").to_string()),
+            CurrentDayNameShort => date.map(|d| d.format("%a").to_string()),
+            CurrentHour => date.map(|d| format!("{:02}", d.hour())),
+            CurrentMinute => date.map(|d| format!("{:02}", d.minute())),
+            CurrentSecond => date.map(|d| format!("{:02}", d.second())),
+            CurrentSecondsUnix => date.map(|d| (d.and_utc().timestamp() - self.timezone_offset as i64 * 60).to_string()),
+            CurrentTimezoneOffset => {
+                let (sign, minutes) = if self.timezone_offset < 0 { ('-', -self.timezone_offset) } else { ('+', self.timezone_offset) };
+                Some(format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60))
+            }
+            Random => {
+                let bytes = self.random_bytes();
+                Some(format!("{:06}", u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) % 1_000_000))
+            }
+            RandomHex => Some(format!("{:02x}{:02x}{:02x}", self.random_bytes()[4], self.random_bytes()[5], self.random_bytes()[6])),
+            Uuid => {
+                let mut b = self.random_bytes();
+                b[6] = (b[6] & 0x0f) | 0x40;
+                b[8] = (b[8] & 0x3f) | 0x80;
+                let hex = b.iter().map(|v| format!("{v:02x}")).collect::<String>();
+                Some(format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..]))
+            }
+            LineComment => comments.map(|c| c.line_or_block().0.to_owned()),
+            BlockCommentStart => comments.map(|c| c.block_or_line().0.to_owned()),
+            BlockCommentEnd => comments.map(|c| c.block_or_line().1.unwrap_or_default().to_owned()),
+        }
+    }
+}
+
+
+/// The expanded tabstop
+/// * index - the tabstop number (the '0' is the final cursor position)
+/// * ranges - the tabstop occurrences in the inserted text (in chars)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandedTabstop {
+    pub index: u32,
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// The snippet expansion result
+/// * text - the inserted text
+/// * tabstops - the tabstops in the navigation order (1, 2, ..., 0)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expansion {
+    pub text: String,
+    pub tabstops: Vec<ExpandedTabstop>,
+}
+
+impl Expansion {
+    /// Get the text of tabstop range
+    /// * range - the tabstop range (in chars)
+    pub fn slice(&self, range: &Range<usize>) -> String {
+        self.text.chars().skip(range.start).take(range.len()).collect()
+    }
+
+    /// Get the tabstop by its number
+    pub fn tabstop(&self, index: u32) -> Option<&ExpandedTabstop> {
+        self.tabstops.iter().find(|t| t.index == index)
+    }
+
+    /// Converts the char offset to zero-based (line, column) position
+    /// * offset - the char offset in the inserted text
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let (mut line, mut column) = (0, 0);
+        for c in self.text.chars().take(offset) {
+            if c == '\n' {
+                line += 1;
+                column = 0;
+            } else {
+                column += 1;
+            }
+        }
+        (line, column)
+    }
+}
+
+impl Snippet {
+    /// Simulates the snippet insertion: resolves the placeholders, choices && variables,
+    /// returns the inserted text && the tabstop ranges
+    /// * ctx - the variables resolver (example: [ExpansionContext])
+    pub fn expand(&self, ctx: &dyn VariableResolver) -> Result<Expansion> {
+        let nodes = self.syntax_tree()?;
+
+        // the first placeholder of each tabstop defines the value of its mirrors:
+        let mut values = vec![];
+        Expander::new(ctx, &[]).collect_values(&nodes, &mut values);
+
+        let mut expander = Expander::new(ctx, &values);
+        expander.expand(&nodes);
+
+        // sorting the tabstops (the final tabstop is the last):
+        let mut tabstops = expander.tabstops;
+        tabstops.sort_by_key(|t| if t.index == 0 { u32::MAX } else { t.index });
+        if tabstops.last().is_none_or(|t| t.index != 0) {
+            let end = expander.len..expander.len;
+            tabstops.push(ExpandedTabstop { index: 0, ranges: vec![end] });
+        }
+
+        Ok(Expansion { text: expander.text, tabstops })
+    }
+}
+
+
+/// The snippet syntax tree expander
+struct Expander<'a> {
+    ctx: &'a dyn VariableResolver,
+    values: &'a [(u32, String)],
+    text: String,
+    len: usize,
+    tabstops: Vec<ExpandedTabstop>,
+    placeholders: Vec<u32>,
+}
+
+impl<'a> Expander<'a> {
+    fn new(ctx: &'a dyn VariableResolver, values: &'a [(u32, String)]) -> Self {
+        Self { ctx, values, text: String::new(), len: 0, tabstops: vec![], placeholders: vec![] }
+    }
+
+    /// Collects the placeholder values, where <(TABSTOP_INDEX, VALUE)>
+    fn collect_values(&self, nodes: &[SnippetNode], values: &mut Vec<(u32, String)>) {
+        for node in nodes {
+            match node {
+                SnippetNode::Placeholder { index, value } => {
+                    if !values.iter().any(|(i, _)| i == index) {
+                        // the nested mirrors get the values collected before:
+                        let known = values.clone();
+                        let mut sub = Expander::new(self.ctx, &known);
+                        sub.expand(value);
+                        values.push((*index, sub.text));
+                    }
+                    self.collect_values(value, values);
+                }
+                SnippetNode::Choice { index, options } if !values.iter().any(|(i, _)| i == index) => {
+                    values.push((*index, options[0].clone()));
+                }
+                SnippetNode::Variable { default: Some(default), .. }
+                    | SnippetNode::UnknownVariable { default: Some(default), .. } => self.collect_values(default, values),
+                _ => {}
+            }
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        self.text.push_str(text);
+        self.len += text.chars().count();
+    }
+
+    /// Adds the tabstop range
+    fn add_range(&mut self, index: u32, range: Range<usize>) {
+        match self.tabstops.iter_mut().find(|t| t.index == index) {
+            Some(tabstop) => tabstop.ranges.push(range),
+            None => self.tabstops.push(ExpandedTabstop { index, ranges: vec![range] }),
+        }
+    }
+
+    fn expand(&mut self, nodes: &[SnippetNode]) {
+        for node in nodes {
+            let start = self.len;
+            match node {
+                SnippetNode::Text(text) => self.push(text),
+                SnippetNode::Tabstop { index, transform } => {
+                    let value = self.value_of(*index);
+                    self.push(&apply_transform(transform.as_ref(), value));
+
+                    // the transformed mirror isn't editable:
+                    if transform.is_none() {
+                        self.add_range(*index, start..self.len);
+                    }
+                }
+                SnippetNode::Placeholder { index, value } => {
+                    // the repeated placeholder mirrors the first one:
+                    if self.placeholders.contains(index) {
+                        let value = self.value_of(*index);
+                        self.push(&value);
+                    } else {
+                        self.placeholders.push(*index);
+                        self.expand(value);
+                    }
+                    self.add_range(*index, start..self.len);
+                }
+                SnippetNode::Choice { index, .. } => {
+                    let value = self.value_of(*index);
+                    self.push(&value);
+                    self.add_range(*index, start..self.len);
+                }
+                SnippetNode::Variable { name, default, transform } => {
+                    match (self.ctx.resolve(*name).filter(|v| !v.is_empty()), default) {
+                        (Some(value), _) => self.push(&apply_transform(transform.as_ref(), value)),
+                        (None, Some(default)) => self.expand(default),
+                        (None, None) => {}
+                    }
+                }
+                SnippetNode::UnknownVariable { name, default, transform } => {
+                    // the transform gets the empty value, otherwise the name is inserted:
+                    match (transform, default) {
+                        (Some(_), _) => self.push(&apply_transform(transform.as_ref(), String::new())),
+                        (None, Some(default)) => self.expand(default),
+                        (None, None) => self.push(name),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the value of tabstop (the first placeholder value)
+    fn value_of(&self, index: u32) -> String {
+        self.values
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, v)| v.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Applies the optional transform to the value (the value is kept if the transform regex is invalid)
+/// * transform - the tabstop or variable transform
+/// * value - the resolved value
+fn apply_transform(transform: Option<&SnippetTransform>, value: String) -> String {
+    match transform {
+        Some(transform) => transform.apply(&value).unwrap_or(value),
+        None => value,
+    }
+}
@@ -1,3 +1,11 @@
 pub mod snippets_doc;    pub use snippets_doc::*;
+pub mod snippets_order;  pub use snippets_order::*;
+#[allow(clippy::module_inception)]
 pub mod snippets;        pub use snippets::*;
 pub mod snippet;         pub use snippet::*;
+pub mod snippet_body;    pub use snippet_body::*;
+pub mod comment_syntax;  pub use comment_syntax::*;
+pub mod variable;        pub use variable::*;
+pub mod transform;       pub use transform::*;
+pub mod syntax;          pub use syntax::*;
+pub mod expansion;       pub use expansion::*;
@@ -1,29 +1,60 @@
 use crate::prelude::*;
+use super::{ SnippetNode, SnippetBody, CommentSyntax };
 use serde::Serialize;
 
 /// The snippet generator
-/// * language - the programming language name
+/// * languages - the programming language names (the snippet scope, empty means the snippets group languages)
 /// * name - the snippet name
 /// * description - the snippet description
-/// * prefix - the snippet prefix
+/// * prefixes - the snippet prefixes (serialized as a string if there is only one)
 /// * body - the snippet body contents
 #[derive(Debug, Clone, Serialize)]
 pub struct Snippet {
-    #[serde(rename = "scope")]
-    pub language: String,
+    #[serde(rename = "scope", serialize_with = "serialize_languages", skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
     #[serde(skip_serializing)]
     pub name: String,
     pub description: String,
-    pub prefix: String,
+    #[serde(rename = "prefix", serialize_with = "serialize_prefixes", skip_serializing_if = "Vec::is_empty")]
+    pub prefixes: Vec<String>,
     pub body: Vec<String>,
 }
 
+/// Serializes the languages list as a comma separated string
+fn serialize_languages<S>(languages: &[String], serializer: S) -> std::result::Result<S::Ok, S::Error>
+where S: serde::Serializer
+{
+    serializer.serialize_str(&languages.join(","))
+}
+
+/// Serializes the prefixes list as a string (if there is only one prefix) or an array
+fn serialize_prefixes<S>(prefixes: &[String], serializer: S) -> std::result::Result<S::Ok, S::Error>
+where S: serde::Serializer
+{
+    match prefixes {
+        [prefix] => serializer.serialize_str(prefix),
+        _ => serializer.collect_seq(prefixes),
+    }
+}
+
 impl Snippet {
-    /// Sets a new snippet programming language name && returns 'Self'
-    /// * lang - a new programming language name
+    /// Sets a new snippet programming language names && returns 'Self'
+    /// * lang - a new programming language name (or comma separated names, example: "javascript,typescript")
     pub fn set_lang<S>(mut self, lang: S) -> Self
     where S: Into<String> {
-        self.language = lang.into();
+        self.languages = split_languages(&lang.into());
+        self
+    }
+
+    /// Adds one more snippet programming language name && returns 'Self'
+    /// * lang - the programming language name
+    pub fn add_lang<S>(mut self, lang: S) -> Self
+    where S: Into<String> {
+        for lang in split_languages(&lang.into()) {
+            if !self.languages.contains(&lang) {
+                self.languages.push(lang);
+            }
+        }
         self
     }
 
@@ -50,24 +81,48 @@ impl Snippet {
         self
     }
 
-    /// Sets a new snippet prefix && returns 'Self'
+    /// Get the main (first) snippet prefix
+    pub fn prefix(&self) -> &str {
+        self.prefixes.first().map_or("", |p| p.as_str())
+    }
+
+    /// Sets a new snippet prefix (replaces all the prefixes) && returns 'Self'
     /// * prefix - a new snippet prefix
     pub fn set_prefix<S>(mut self, prefix: S) -> Self
     where S: Into<String> {
-        self.prefix = prefix.into();
+        self.prefixes = vec![prefix.into()];
         self
     }
-    
-    /// Sets a new body contents of snippet && returns 'Self'
-    /// * body - a new body contents
-    pub fn set_body<S>(mut self, body: Vec<S>) -> Self
+
+    /// Sets a new snippet prefixes list && returns 'Self'
+    /// * prefixes - a new snippet prefixes
+    pub fn set_prefixes<S>(mut self, prefixes: Vec<S>) -> Self
     where S: Into<String> {
-        self.body = body
+        self.prefixes = prefixes
             .into_iter()
             .map(|v| v.into())
             .collect::<Vec<_>>();
         self
     }
+
+    /// Adds one more snippet prefix (the alias) && returns 'Self'
+    /// * prefix - the additional prefix
+    pub fn add_prefix<S>(mut self, prefix: S) -> Self
+    where S: Into<String> {
+        let prefix = prefix.into();
+        if !self.prefixes.contains(&prefix) {
+            self.prefixes.push(prefix);
+        }
+        self
+    }
+    
+    /// Sets a new body contents of snippet && returns 'Self'
+    /// * body - a new body contents (the [SnippetBody] builder or raw snippet syntax lines)
+    pub fn set_body<B>(mut self, body: B) -> Self
+    where B: Into<SnippetBody> {
+        self.body = body.into().into();
+        self
+    }
     
     /// Adds a new line to snippet body && returns 'Self'
     /// * text - a line contents
@@ -109,18 +164,15 @@ impl Snippet {
     /// * name - the snippet name id
     /// * descr - the snippet description
     /// * prefix - the snippet prefix
-    /// * body - the body of snippet
-    pub fn new<S>(name: S, descr: S, prefix: S, body: Vec<S>) -> Self
-    where S: Into<String> {
+    /// * body - the body of snippet (the [SnippetBody] builder or raw snippet syntax lines)
+    pub fn new<S, B>(name: S, descr: S, prefix: S, body: B) -> Self
+    where S: Into<String>, B: Into<SnippetBody> {
         Self {
-            language: "".into(),
+            languages: vec![],
             name: name.into(),
             description: descr.into(),
-            prefix: prefix.into(),
-            body: body
-                .into_iter()
-                .map(|s| s.into())
-                .collect::<Vec<_>>()
+            prefixes: Some(prefix.into()).filter(|p| !p.is_empty()).into_iter().collect(),
+            body: body.into().into(),
         }
     }
     
@@ -132,47 +184,96 @@ impl Snippet {
     where S: Into<String> {
         let value = value.into();
         
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: value.clone(),
-            prefix: prefix.into(),
-            body: vec![
-                value
-            ]
-        }
+        Self::new(
+            name.into(),
+            value.clone(),
+            prefix.into(),
+            SnippetBody::new().text(&value),
+        )
     }
     
-    /// Creates a new comment snippet
+    /// Creates a new line comment snippet (uses '$LINE_COMMENT' variable of the current language)
     /// * name - the snippet name id
     /// * cmnt_name - the comment name
     pub fn comment<S>(name: S, cmnt_name: &str) -> Self
     where S: Into<String> {
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: format!("// {cmnt_name}: ..."),
-            prefix: format!("/{cmnt_name}"),
-            body: vec![
-                format!("// {cmnt_name}: {}", "${1:...}")
-            ]
-        }
+        Self::comment_with(name.into(), format!("/{cmnt_name}"), cmnt_name, None, ("LINE_COMMENT", None))
+    }
+
+    /// Creates a new line comment snippet for the language (uses the block comment if the language has no line comments)
+    /// * name - the snippet name id
+    /// * lang - the language id for [comment syntax](CommentSyntax) (the unknown language uses '$LINE_COMMENT' variable)
+    /// * cmnt_name - the comment name
+    pub fn comment_for<S>(name: S, lang: &str, cmnt_name: &str) -> Self
+    where S: Into<String> {
+        let tokens = CommentSyntax::of(lang).map(|syntax| syntax.line_or_block());
+        Self::comment_with(name.into(), format!("/{cmnt_name}"), cmnt_name, tokens, ("LINE_COMMENT", None))
+    }
+
+    /// Creates a new block comment snippet (uses '$BLOCK_COMMENT_START' && '$BLOCK_COMMENT_END' variables of the current language)
+    /// * name - the snippet name id
+    /// * cmnt_name - the comment name
+    pub fn block_comment<S>(name: S, cmnt_name: &str) -> Self
+    where S: Into<String> {
+        Self::comment_with(name.into(), format!("/*{cmnt_name}"), cmnt_name, None, ("BLOCK_COMMENT_START", Some("BLOCK_COMMENT_END")))
+    }
+
+    /// Creates a new block comment snippet for the language (uses the line comment if the language has no block comments)
+    /// * name - the snippet name id
+    /// * lang - the language id for [comment syntax](CommentSyntax) (the unknown language uses '$BLOCK_COMMENT_START' && '$BLOCK_COMMENT_END' variables)
+    /// * cmnt_name - the comment name
+    pub fn block_comment_for<S>(name: S, lang: &str, cmnt_name: &str) -> Self
+    where S: Into<String> {
+        let tokens = CommentSyntax::of(lang).map(|syntax| syntax.block_or_line());
+        Self::comment_with(name.into(), format!("/*{cmnt_name}"), cmnt_name, tokens, ("BLOCK_COMMENT_START", Some("BLOCK_COMMENT_END")))
+    }
+
+    /// Creates a new comment snippet with the comment tokens
+    /// * name - the snippet name id
+    /// * prefix - the snippet prefix
+    /// * cmnt_name - the comment name
+    /// * tokens - the comment start && end tokens (if 'None', then the variables are used)
+    /// * vars - the comment start && end variable names
+    fn comment_with(name: String, prefix: String, cmnt_name: &str, tokens: Option<(&str, Option<&str>)>, vars: (&str, Option<&str>)) -> Self {
+        let body = match tokens {
+            Some((start, _)) => SnippetBody::new().text(&format!("{start} ")),
+            None => SnippetBody::new().variable(vars.0, None).text(" "),
+        };
+        let body = body.text(&format!("{cmnt_name}: ")).placeholder(1, "...");
+        let body = match tokens {
+            Some((_, Some(end))) => body.text(&format!(" {end}")),
+            Some((_, None)) => body,
+            None => match vars.1 {
+                Some(end) => body.text(" ").variable(end, None),
+                None => body,
+            },
+        };
+
+        let descr = match tokens {
+            Some((start, end)) => format!("{start} {cmnt_name}: ...{}", end.map(|e| format!(" {e}")).unwrap_or_default()),
+            None => format!("{cmnt_name}: ..."),
+        };
+
+        Self::new(name, descr, prefix, body)
     }
 
     /// Creates a new attribute snippet (for Rust lang)
     /// * name - the snippet name
     /// * attr_name - the attiribute name
+    /// * values - the attribute argument choices (the empty list makes a simple tabstop)
     pub fn attribute<S>(name: S, attr_name: &str, values: Option<Vec<&str>>) -> Self
     where S: Into<String> {
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: format!("#[{attr_name}{}]", if values.is_some(){ "(...)" }else{ "" }),
-            prefix: format!("#[{attr_name}{}]", if values.is_some(){ "()" }else{ "" }),
-            body: vec![
-                format!("#[{attr_name}{}]", if let Some(values) = values{ if !values.is_empty(){ "(${1|".to_owned() + &values.join(",") + "|})"}else{ "($1)".to_owned() } }else{ "".into() })
-            ]
+        let mut body = SnippetBody::new().text(&format!("#[{attr_name}"));
+        if let Some(values) = &values {
+            body = body.text("(").choice(1, values).text(")");
         }
+
+        Self::new(
+            name.into(),
+            format!("#[{attr_name}{}]", if values.is_some(){ "(...)" }else{ "" }),
+            format!("#[{attr_name}{}]", if values.is_some(){ "()" }else{ "" }),
+            body.text("]"),
+        )
     }
 
     /// Creates a new block snippet
@@ -182,15 +283,15 @@ impl Snippet {
     where S: Into<String> {
         let block_name = block_name.into();
         
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: format!("{block_name} ... {}", "{ ... }"),
-            prefix: block_name.clone() + " {}",
-            body: vec![
-                format!("{block_name} $1 {}", "{\n    $2\n}")
-            ]
-        }
+        Self::new(
+            name.into(),
+            format!("{block_name} ... {}", "{ ... }"),
+            block_name.clone() + " {}",
+            SnippetBody::new()
+                .text(&format!("{block_name} ")).tabstop(1).text(" {")
+                .newline().text("    ").tabstop(2)
+                .newline().text("}"),
+        )
     }
 
     /// Creates a new double block snippet
@@ -199,17 +300,19 @@ impl Snippet {
     /// * other_block_name - the other block name
     pub fn double_block<S>(name: S, first_block_name: &str, other_block_name: &str) -> Self
     where S: Into<String> {
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: format!("{first_block_name} ... {}  {other_block_name} ... {0}", "{ ... }"),
-            prefix: first_block_name.to_owned() + " {}" + " " + &other_block_name + " {}",
-            body: vec![
-                format!("{first_block_name} $1 {}", "{\n    $2\n}"),
-                "".to_owned(),
-                format!("{other_block_name} $1 {0}", "{\n    $3\n}")
-            ]
-        }
+        Self::new(
+            name.into(),
+            format!("{first_block_name} ... {}  {other_block_name} ... {0}", "{ ... }"),
+            first_block_name.to_owned() + " {}" + " " + other_block_name + " {}",
+            SnippetBody::new()
+                .text(&format!("{first_block_name} ")).tabstop(1).text(" {")
+                .newline().text("    ").tabstop(2)
+                .newline().text("}")
+                .newline()
+                .newline().text(&format!("{other_block_name} ")).tabstop(1).text(" {")
+                .newline().text("    ").tabstop(3)
+                .newline().text("}"),
+        )
     }
 
     /// Creates a new simple block snippet without arguments
@@ -217,15 +320,15 @@ impl Snippet {
     /// * block_name - the block name
     pub fn simple_block<S>(name: S, block_name: &str) -> Self
     where S: Into<String> {
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: format!("{block_name} {}", "{ ... }"),
-            prefix: block_name.to_owned() + " {}",
-            body: vec![
-                format!("{block_name} {}", "{\n    $1\n}"),
-            ]
-        }
+        Self::new(
+            name.into(),
+            format!("{block_name} {}", "{ ... }"),
+            block_name.to_owned() + " {}",
+            SnippetBody::new()
+                .text(&format!("{block_name} {{"))
+                .newline().text("    ").tabstop(1)
+                .newline().text("}"),
+        )
     }
 
     /// Creates a new function block snippet
@@ -233,15 +336,15 @@ impl Snippet {
     /// * name - the block name
     pub fn function_block<S>(name: S, block_name: &str) -> Self
     where S: Into<String> {
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: format!("{block_name} ...() {}", "{ ... }"),
-            prefix: block_name.to_owned() + "() {}",
-            body: vec![
-                format!("{block_name} $1($2) {}", "{\n    $3\n}"),
-            ]
-        }
+        Self::new(
+            name.into(),
+            format!("{block_name} ...() {}", "{ ... }"),
+            block_name.to_owned() + "() {}",
+            SnippetBody::new()
+                .text(&format!("{block_name} ")).tabstop(1).text("(").tabstop(2).text(") {")
+                .newline().text("    ").tabstop(3)
+                .newline().text("}"),
+        )
     }
 
     /// Creates a new operator snippet without arguments
@@ -249,15 +352,17 @@ impl Snippet {
     /// * oper_name - the operator name
     pub fn operator<S>(name: S, oper_name: &str, value: Option<&str>) -> Self
     where S: Into<String> {
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: if value.is_some() { format!("{oper_name} ...;") }else{ format!("{oper_name};") },
-            prefix: if value.is_some() { format!("{oper_name} ") }else{ format!("{oper_name}") },
-            body: vec![
-                if let Some(value) = value { format!("{oper_name} ${}1:{value}{};", "{", "}") }else{ format!("{oper_name};") },
-            ]
-        }
+        let body = match value {
+            Some(value) => SnippetBody::new().text(&format!("{oper_name} ")).placeholder(1, value).text(";"),
+            None => SnippetBody::new().text(&format!("{oper_name};")),
+        };
+
+        Self::new(
+            name.into(),
+            if value.is_some() { format!("{oper_name} ...;") }else{ format!("{oper_name};") },
+            if value.is_some() { format!("{oper_name} ") }else{ oper_name.to_string() },
+            body,
+        )
     }
 
     /// Creates a new function snippet
@@ -266,21 +371,115 @@ impl Snippet {
     /// * pars - the non standart function parenthesis
     pub fn function<S>(name: S, fn_name: &str, pars: Option<(&str, &str)>, value: Option<&str>) -> Self
     where S: Into<String> {
-        let (lpar, rpar) = if let Some(pars) = pars { pars }else{ ("(", ")") };
-        
-        Self {
-            language: "".into(),
-            name: name.into(),
-            description: format!("{fn_name}{lpar}{}{rpar}", if value.is_some(){ "..." }else{ "" }),
-            prefix: fn_name.to_owned() + lpar + rpar,
-            body: vec![
-                if let Some(value) = value { format!("{fn_name}{lpar}${}1:{value}{}{rpar}", "{", "}") }else{ format!("{fn_name}{lpar}{rpar}") },
-            ]
+        let (lpar, rpar) = pars.unwrap_or(("(", ")"));
+
+        let mut body = SnippetBody::new().text(&format!("{fn_name}{lpar}"));
+        if let Some(value) = value {
+            body = body.placeholder(1, value);
         }
+
+        Self::new(
+            name.into(),
+            format!("{fn_name}{lpar}{}{rpar}", if value.is_some(){ "..." }else{ "" }),
+            fn_name.to_owned() + lpar + rpar,
+            body.text(rpar),
+        )
     }
 
     /// Converts the snippet to JSON string
     pub fn to_json(&self) -> Result<String> {
         serde_json::to_string_pretty(&self).map_err(Error::from)
     }
+
+    /// Creates the snippet from the VS Code snippet JSON object
+    /// (returns [Error::InvalidSnippetJson] if it can't be converted, or [Error::InvalidSnippet] if its body is invalid)
+    /// * name - the snippet name (the key in the snippets file)
+    /// * value - the snippet JSON object with 'prefix', 'body', 'description' && 'scope' fields
+    pub fn from_json_value(name: &str, value: &serde_json::Value) -> Result<Self> {
+        use serde_json::Value;
+
+        let invalid = |error: &str| Error::InvalidSnippetJson { name: name.into(), error: error.into() };
+        let obj = value.as_object().ok_or_else(|| invalid("the snippet is not a JSON object"))?;
+
+        // reading the string or strings array field:
+        let strings = |field: &str| -> Result<Option<Vec<String>>> {
+            match obj.get(field) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(s)) => Ok(Some(vec![s.clone()])),
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .map(|v| v.as_str().map(String::from).ok_or_else(|| invalid(&format!("the '{field}' array must contain only strings"))))
+                    .collect::<Result<Vec<_>>>()
+                    .map(Some),
+                Some(_) => Err(invalid(&format!("the '{field}' must be a string or an array of strings"))),
+            }
+        };
+
+        let body = strings("body")?.ok_or_else(|| invalid("the 'body' field is missing"))?;
+        let prefixes = strings("prefix")?.unwrap_or_default();
+        let descr = match obj.get("description") {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(_) => return Err(invalid("the 'description' must be a string")),
+        };
+        let scope = match obj.get("scope") {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(_) => return Err(invalid("the 'scope' must be a string")),
+        };
+
+        let body = body
+            .iter()
+            .flat_map(|line| line.split('\n'))
+            .map(|line| line.trim_end_matches('\r'))
+            .collect::<Vec<_>>();
+
+        let snippet = Self::new(name, &descr, "", body)
+            .set_prefixes(prefixes)
+            .set_lang(scope);
+
+        // checking the body syntax:
+        snippet.validate()?;
+
+        Ok(snippet)
+    }
+
+    /// Parses the snippet body to syntax tree
+    pub fn syntax_tree(&self) -> Result<Vec<SnippetNode>> {
+        SnippetNode::parse(&self.body.join("\n"))
+            .map_err(|error| Error::InvalidSnippet { name: self.name.clone(), error })
+    }
+
+    /// Checks the snippet body syntax (unbalanced braces, malformed choices, transforms, etc.)
+    pub fn validate(&self) -> Result<()> {
+        self.syntax_tree().map(|_| ())
+    }
+
+    /// Get the variable names which aren't in the [documented list](super::SnippetVariable)
+    /// (VS Code inserts such name as a placeholder text)
+    pub fn unknown_variables(&self) -> Result<Vec<String>> {
+        let mut names = vec![];
+        collect_unknown_variables(&self.syntax_tree()?, &mut names);
+        Ok(names)
+    }
+}
+
+/// Collects the unknown variable names (without duplicates)
+/// * nodes - the snippet syntax tree
+/// * names - the found names
+fn collect_unknown_variables(nodes: &[SnippetNode], names: &mut Vec<String>) {
+    for node in nodes {
+        match node {
+            SnippetNode::UnknownVariable { name, default, .. } => {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+                if let Some(default) = default {
+                    collect_unknown_variables(default, names);
+                }
+            }
+            SnippetNode::Placeholder { value, .. } | SnippetNode::Variable { default: Some(value), .. } => collect_unknown_variables(value, names),
+            _ => {}
+        }
+    }
 }
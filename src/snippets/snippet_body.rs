@@ -0,0 +1,168 @@
+use super::SnippetTransform;
+
+/// The snippet body builder (escapes the special symbols automatically)
+/// * lines - the body lines in VS Code snippet syntax
+///
+/// Example: `SnippetBody::new().text("fn ").tabstop(1).text("() {").newline().text("    ").final_cursor().newline().text("}")`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnippetBody {
+    lines: Vec<String>,
+}
+
+impl SnippetBody {
+    /// Creates a new empty snippet body
+    pub fn new() -> Self {
+        Self {
+            lines: vec![String::new()],
+        }
+    }
+
+    /// Appends the raw snippet syntax to the last line (without escaping, '\n' starts a new line)
+    /// * value - the raw snippet syntax
+    pub fn raw(mut self, value: &str) -> Self {
+        let mut parts = value.split('\n');
+        if let Some(first) = parts.next() {
+            self.last_line().push_str(first);
+        }
+        self.lines.extend(parts.map(String::from));
+        self
+    }
+
+    /// Appends the plain text (the '\n' starts a new line)
+    /// * text - the plain text
+    pub fn text(self, text: &str) -> Self {
+        let escaped = escape(text, &['$']);
+        self.raw(&escaped)
+    }
+
+    /// Starts a new body line
+    pub fn newline(mut self) -> Self {
+        self.lines.push(String::new());
+        self
+    }
+
+    /// Appends the tabstop `$N`
+    /// * index - the tabstop number
+    pub fn tabstop(self, index: u32) -> Self {
+        self.raw(&format!("${index}"))
+    }
+
+    /// Appends the placeholder `${N:default}`
+    /// * index - the tabstop number
+    /// * default - the placeholder default text
+    pub fn placeholder(self, index: u32, default: &str) -> Self {
+        let default = escape(default, &['$', '}']);
+        self.raw(&format!("${{{index}:{default}}}"))
+    }
+
+    /// Appends the choice `${N|one,two,three|}` (the empty choice list becomes a tabstop `$N`)
+    /// * index - the tabstop number
+    /// * options - the choice options
+    pub fn choice<S>(self, index: u32, options: &[S]) -> Self
+    where S: AsRef<str> {
+        let options = options
+            .iter()
+            .map(|v| v.as_ref())
+            .filter(|v| !v.is_empty())
+            .map(|v| escape(v, &[',', '|', '\\']))
+            .collect::<Vec<_>>();
+
+        if options.is_empty() {
+            return self.tabstop(index);
+        }
+
+        self.raw(&format!("${{{index}|{}|}}", options.join(",")))
+    }
+
+    /// Appends the variable `${NAME}` or `${NAME:default}`
+    /// * name - the variable name (example: "TM_FILENAME")
+    /// * default - the default text if the variable is not set
+    pub fn variable<V>(self, name: V, default: Option<&str>) -> Self
+    where V: AsRef<str> {
+        let name = name.as_ref();
+        match default {
+            Some(default) => {
+                let default = escape(default, &['$', '}']);
+                self.raw(&format!("${{{name}:{default}}}"))
+            }
+            None => self.raw(&format!("${{{name}}}")),
+        }
+    }
+
+    /// Appends the transformed tabstop mirror `${N/regex/format/options}`
+    /// * index - the tabstop number
+    /// * transform - the regex transform of tabstop value
+    pub fn tabstop_transform(self, index: u32, transform: &SnippetTransform) -> Self {
+        self.raw(&format!("${{{index}{transform}}}"))
+    }
+
+    /// Appends the transformed variable `${NAME/regex/format/options}`
+    /// * name - the variable name (example: "TM_FILENAME")
+    /// * transform - the regex transform of variable value
+    pub fn variable_transform<V>(self, name: V, transform: &SnippetTransform) -> Self
+    where V: AsRef<str> {
+        self.raw(&format!("${{{}{transform}}}", name.as_ref()))
+    }
+
+    /// Appends the final cursor position `$0`
+    pub fn final_cursor(self) -> Self {
+        self.tabstop(0)
+    }
+
+    /// Get the body lines
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Get the mutable last line
+    fn last_line(&mut self) -> &mut String {
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.lines.last_mut().unwrap()
+    }
+}
+
+impl std::default::Default for SnippetBody {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> From<Vec<S>> for SnippetBody
+where S: Into<String> {
+    /// Creates the body from the raw snippet syntax lines
+    fn from(lines: Vec<S>) -> Self {
+        Self {
+            lines: lines.into_iter().map(|v| v.into()).collect(),
+        }
+    }
+}
+
+impl From<SnippetBody> for Vec<String> {
+    fn from(body: SnippetBody) -> Self {
+        body.lines
+    }
+}
+
+/// Escapes the special symbols by backslash
+/// * text - the plain text
+/// * specials - the symbols to escape (if the backslash is not listed, it's escaped only when it can be confused with an escape sequence)
+fn escape(text: &str, specials: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let need_escape = if c == '\\' && !specials.contains(&c) {
+            // the backslash needs escaping before the '$', '}', '\' symbols or at the end:
+            chars.peek().is_none_or(|next| matches!(next, '$' | '}' | '\\'))
+        } else {
+            specials.contains(&c)
+        };
+
+        if need_escape { escaped.push('\\'); }
+        escaped.push(c);
+    }
+
+    escaped
+}
@@ -1,27 +1,44 @@
 use crate::prelude::*;
-use super::{ Snippet, SnippetsDoc };
-use std::{fs, path::PathBuf};
+use super::{ Snippet, SnippetsDoc, SnippetsOrder };
+use std::{fs, path::{ Path, PathBuf }};
+
+/// The snippet which could not be imported from snippets file
+/// * file - the snippets file path (empty if the snippets aren't read from file)
+/// * key - the snippet key in the snippets file
+/// * reason - the reason why the snippet was skipped
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedSnippet {
+    pub file: PathBuf,
+    pub key: String,
+    pub reason: String,
+}
 
 /// The snippets group
-/// * language - the programming language name
+/// * languages - the programming language names
 /// * name - the snippets group name
 /// * description - the snippets group description
 /// * file_name - the snippets file name
-/// * snippets - the snippets hash map, where <SNIPPET_NAME, SNIPPET>
+/// * snippets - the snippets list in insertion order, where <(SNIPPET_KEY, SNIPPET)>
+/// * order - the snippets [order](SnippetsOrder) in the snippets file && documentation
+/// * renamed - the disambiguated snippet keys, where <(NAME_KEY, ASSIGNED_KEY)>
+/// * inject_scopes - set the group languages as 'scope' of snippets without own scope (is off for the imported groups)
 /// * documentation - the snippets group [documentation](SnippetsDoc) generator
 #[derive(Debug, Clone)]
 pub struct Snippets {
-    pub language: String,
+    pub languages: Vec<String>,
     pub name: String,
     pub description: String,
     pub file_name: PathBuf,
-    snippets: HashMap<String, Snippet>,
+    snippets: Vec<(String, Snippet)>,
+    order: SnippetsOrder,
+    renamed: Vec<(String, String)>,
+    inject_scopes: bool,
     documentation: SnippetsDoc,
 }
 
 impl Snippets {
     /// Creates a new 'Snippets' object
-    /// * language - the programming language name
+    /// * language - the programming language name (or comma separated names, example: "javascript,typescript")
     /// * name - the snippets group name
     /// * description - the snippets group description
     /// * snippets - the snippets list
@@ -35,11 +52,14 @@ impl Snippets {
 
         // creating the 'Snippets' object:
         let mut this = Self {
-            language: lang.clone(),
+            languages: split_languages(&lang),
             name: to_latin_text(&name, true),
             description: descr.clone(),
             file_name,
-            snippets: HashMap::new(),
+            snippets: vec![],
+            order: SnippetsOrder::default(),
+            renamed: vec![],
+            inject_scopes: true,
             documentation: SnippetsDoc::new(lang, name, descr)
         };
 
@@ -51,14 +71,46 @@ impl Snippets {
         this
     }
 
-    /// Sets the snippets programming language name
-    /// * lang - the programming language name
+    /// Sets the snippets programming language names
+    /// * lang - the programming language name (or comma separated names, example: "javascript,typescript")
     pub fn set_lang<S>(mut self, lang: S) -> Self
     where S: Into<String> {
-        self.language = lang.into();
+        self.languages = split_languages(&lang.into());
         self
     }
 
+    /// Get the languages the snippets file is contributed for (the empty list means the file is contributed without language)
+    ///
+    /// VS Code ignores the snippets own scopes if the file is contributed for a language,
+    /// so the ".code-snippets" file is contributed without language unless all its snippets have the single group language
+    /// (the imported groups keep their contributed languages)
+    pub fn contributed_languages(&self) -> Vec<String> {
+        let scoped = self.file_name.extension().is_some_and(|ext| ext == "code-snippets");
+        if !scoped || !self.inject_scopes {
+            return self.languages.clone();
+        }
+
+        let single = self.languages.len() == 1 && self.snippets
+            .iter()
+            .all(|(_, s)| s.languages.is_empty() || s.languages == self.languages);
+        if single { self.languages.clone() } else { vec![] }
+    }
+
+    /// Get the languages where VS Code shows the snippet (the empty list means all languages)
+    /// * snippet - the snippet of this group
+    pub fn snippet_languages(&self, snippet: &Snippet) -> Vec<String> {
+        let contributed = self.contributed_languages();
+        if !contributed.is_empty() {
+            contributed
+        } else if !snippet.languages.is_empty() {
+            snippet.languages.clone()
+        } else if self.inject_scopes {
+            self.languages.clone()
+        } else {
+            vec![]
+        }
+    }
+
     /// Sets the snippets group name
     /// * name - the snippets group name
     pub fn set_name<S>(mut self, name: S) -> Self
@@ -83,48 +135,181 @@ impl Snippets {
         self
     }
 
-    /// Adds a new snippet
-    pub fn add_snippet(&mut self, snippet: Snippet) {
-        // write snippet info to documentation:
-        self.documentation.write_line(snippet.prefix.clone(), snippet.description.clone());
-        
-        // generate new unique snippet name:
-        let name = to_latin_text(snippet.name.trim(), true) + "-" + &gen_unique_name(6);
+    /// Sets the snippets order in the snippets file && documentation
+    /// * order - the snippets order
+    pub fn set_order(mut self, order: SnippetsOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Adds a new snippet && returns its key in the snippets file
+    /// (the key is derived from the snippet name, the clashing names get the suffix "-2", "-3", etc.)
+    pub fn add_snippet(&mut self, snippet: Snippet) -> String {
+        // generate the snippet key from its name:
+        let name = to_latin_text(snippet.name.trim(), true);
+        self.insert_snippet(name, snippet)
+    }
+
+    /// Adds a new snippet with the given key && returns its key in the snippets file
+    /// (the key is kept as is, the clashing keys get the suffix "-2", "-3", etc.)
+    /// * key - the snippet key (example: "Print to console")
+    /// * snippet - the snippet
+    pub fn insert_snippet<S>(&mut self, key: S, snippet: Snippet) -> String
+    where S: Into<String>
+    {
+        let mut name = key.into();
+        if name.is_empty() { name = "snippet".into(); }
+
+        // disambiguate the clashing key:
+        let mut key = name.clone();
+        let mut n = 2;
+        while self.get(&key).is_some() {
+            key = format!("{name}-{n}");
+            n += 1;
+        }
+        if key != name {
+            self.renamed.push((name, key.clone()));
+        }
+
         // adding snippet:
-        self.snippets.insert(name, snippet);
+        self.snippets.push((key.clone(), snippet));
+        key
+    }
+
+    /// Get the snippet by its key
+    pub fn get(&self, key: &str) -> Option<&Snippet> {
+        self.snippets
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, s)| s)
+    }
+
+    /// Iterates the snippets in insertion order, where <(SNIPPET_KEY, SNIPPET)>
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Snippet)> {
+        self.snippets.iter().map(|(k, s)| (k, s))
+    }
+
+    /// Get the snippets sorted by the group order, where <(SNIPPET_KEY, SNIPPET)>
+    pub fn sorted(&self) -> Vec<(&String, &Snippet)> {
+        let mut snippets = self.iter().collect::<Vec<_>>();
+        self.order.sort(&mut snippets);
+        snippets
+    }
+
+    /// Generates the snippets group documentation (in the group order)
+    pub fn documentation(&self) -> SnippetsDoc {
+        let mut doc = self.documentation.clone();
+        doc.language = self.languages.join(", ");
+        doc.clear_body();
+
+        for (_, snippet) in self.sorted() {
+            doc.write_line(snippet.prefixes.join(", "), snippet.description.clone());
+        }
+
+        doc
+    }
+
+    /// Get the snippet keys that were disambiguated because of clashing names, where <(NAME_KEY, ASSIGNED_KEY)>
+    pub fn renamed_keys(&self) -> &[(String, String)] {
+        &self.renamed
+    }
+
+    /// Checks the body syntax of all snippets in group
+    pub fn validate(&self) -> Result<()> {
+        for (_, snippet) in &self.snippets {
+            snippet.validate()?;
+        }
+
+        Ok(())
     }
 
     /// Converts the snippets group to JSON string
     pub fn to_json(&self) -> Result<String> {
         // check && set the snippets programming language name:
-        let mut snippets = self.snippets.clone();
-        for (_, snippet) in snippets.iter_mut() {
-            if snippet.language.is_empty() {
-                snippet.language = self.language.clone();
-            }
-        }
+        let snippets = self.sorted()
+            .into_iter()
+            .map(|(key, snippet)| {
+                let mut snippet = snippet.clone();
+                if snippet.languages.is_empty() && self.inject_scopes {
+                    snippet.languages = self.languages.clone();
+                }
+                (key, snippet)
+            })
+            .collect::<Vec<_>>();
 
-        // Converting snippets group to JSON string && Write it to file:
-        serde_json::to_string_pretty(&snippets).map_err(Error::from)
+        // Converting snippets group to JSON object (keeping the order):
+        let map = OrderedMap(snippets);
+        serde_json::to_string_pretty(&map).map_err(Error::from)
     }
     
     /// Writes the snippets group to file "%DIR/snippets/%FILE_NAME.code-snippets"
     /// * dir - the package root directory path (without '/snippets' folder)
-    pub fn write_to<P>(&self, dir: P) -> Result<&SnippetsDoc>
+    pub fn write_to<P>(&self, dir: P) -> Result<SnippetsDoc>
     where P: Into<PathBuf>
     {
-        // creating dir path:
-        let dir = dir.into().join("snippets");
-        // dbg!(&dir);  // DEBUG:
-        fs::create_dir_all(&dir).map_err(Error::from)?;
-
-        // converting snippets to JSON string:
-        let json_contents = self.to_json()?;
-
-        // writing snippets to file:
-        let path = dir.join(&self.file_name);
-        fs::write(path, json_contents).map_err(Error::from)?;
-        
-        Ok(&self.documentation)
+        self.write_into(&mut crate::FsSink::new(dir))
+    }
+
+    /// Writes the snippets file "snippets/<FILE_NAME>" to output && returns the documentation
+    /// * sink - the package output
+    pub fn write_into(&self, sink: &mut dyn crate::OutputSink) -> Result<SnippetsDoc> {
+        let path = Path::new("snippets").join(&self.file_name);
+        sink.write_file(&path, self.to_json()?.as_bytes())?;
+
+        Ok(self.documentation())
+    }
+}
+
+impl Snippets {
+    /// Imports the snippets group from VS Code snippets JSON (the comments && trailing commas are allowed),
+    /// returns the group && the list of skipped snippets which could not be converted
+    /// (the snippet keys && scopes are kept as is, so the group is written back unchanged)
+    /// * language - the programming language name (or comma separated names)
+    /// * name - the snippets group name
+    /// * description - the snippets group description
+    /// * json - the snippets file contents
+    pub fn from_json<S>(language: S, name: S, description: S, json: &str) -> Result<(Self, Vec<SkippedSnippet>)>
+    where S: Into<String>
+    {
+        let entries: OrderedMap<String, serde_json::Value> = serde_json::from_str(&strip_json_comments(json))?;
+
+        let mut this = Self::new(language, name, description, vec![]);
+        this.inject_scopes = false;
+        let mut skipped = vec![];
+
+        for (key, value) in entries.0 {
+            let reason = match Snippet::from_json_value(&key, &value) {
+                Ok(snippet) => { this.insert_snippet(key, snippet); continue; }
+                Err(Error::InvalidSnippet { error, .. }) => format!("invalid body: {error}"),
+                Err(Error::InvalidSnippetJson { error, .. }) => error,
+                Err(e) => e.to_string(),
+            };
+            skipped.push(SkippedSnippet { file: PathBuf::new(), key, reason });
+        }
+
+        Ok((this, skipped))
+    }
+
+    /// Imports the snippets group from VS Code snippets file (".code-snippets" or "%LANG.json"),
+    /// returns the group && the list of skipped snippets which could not be converted
+    /// * language - the programming language name (or comma separated names)
+    /// * path - the snippets file path (the file name is used as a group name)
+    pub fn from_file<S, P>(language: S, path: P) -> Result<(Self, Vec<SkippedSnippet>)>
+    where S: Into<String>, P: AsRef<Path>
+    {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)?;
+        let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+
+        let (this, mut skipped) = Self::from_json(language.into(), name, String::new(), &json)?;
+        for skip in &mut skipped {
+            skip.file = path.to_path_buf();
+        }
+        let this = match path.file_name() {
+            Some(file_name) => this.set_file_name(file_name),
+            None => this,
+        };
+
+        Ok((this, skipped))
     }
 }
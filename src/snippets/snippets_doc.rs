@@ -1,5 +1,7 @@
+use crate::tools::escape_table_cell;
+
 /// The snippets documentation generator
-/// * language - the snippets programming language name
+/// * language - the snippets programming language names
 /// * title - the snippets group name
 /// * description - the snippets group description
 /// * body - the documentation body, where '<(SNIPPET_PREFIX, SNIPPET_DESCRIPTION)>'
@@ -33,21 +35,31 @@ impl SnippetsDoc {
     }
     
     /// Writing a new line to documentation body
-    /// * prefix - the snippet prefix text
+    /// * prefix - the snippet prefix text (all the snippet prefixes)
     /// * descr - the snippet description
-    /// ```no_run .write_line("st", "struct Name { ... }");```
+    ///
+    /// Example: `.write_line("st", "struct Name { ... }");`
     pub fn write_line<S>(&mut self, prefix: S, descr: S)
     where S: Into<String>
     {
         self.body.push((prefix.into(), descr.into()));
     }
 
+}
+
+impl std::fmt::Display for SnippetsDoc {
     /// Converts the documentation object to string
-    pub fn to_string(&self) -> String {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // escaping the table cells:
+        let body = self.body
+            .iter()
+            .map(|(prefix, descr)| (escape_table_cell(prefix), escape_table_cell(descr)))
+            .collect::<Vec<_>>();
+
         // calculating the maximum of table length:
         // | ____l is prefix____ | ____r is description____ |
         let (mut l_max, mut r_max) = (0, 0);
-        for (l, r) in &self.body {
+        for (l, r) in &body {
             let (l_len, r_len) = (l.len() + 2, r.len() + 2);
             
             if l_len > l_max { l_max = l_len; }
@@ -60,33 +72,33 @@ impl SnippetsDoc {
             lang = &self.language,
             title = &self.title,
             descr = &self.description,
-            l_spaces = " ".repeat(if l_max >= 7 { l_max - 7 }else{ 0 }),
-            r_spaces = " ".repeat(if r_max >= 12 { r_max - 12 }else{ 0 }),
+            l_spaces = " ".repeat(l_max.saturating_sub(7)),
+            r_spaces = " ".repeat(r_max.saturating_sub(12)),
             l_dashes = "-".repeat(l_max),
             r_dashes = "-".repeat(r_max)
         );
 
         // adding the snippet instructions to documentation:
-        for (prefix, descr) in &self.body {
+        for (prefix, descr) in &body {
             let (l_len, r_len) = (prefix.len(), descr.len());
             
             // generating the table line:
             let line = format!(
                 "| {prefix}{l_spaces} | {descr}{r_spaces} |\n",
-                l_spaces = " ".repeat(if l_max >= l_len { l_max - l_len }else{ 0 }),
-                r_spaces = " ".repeat(if r_max >= r_len { r_max - r_len }else{ 0 }),
+                l_spaces = " ".repeat(l_max.saturating_sub(l_len)),
+                r_spaces = " ".repeat(r_max.saturating_sub(r_len)),
             );
 
             doc.push_str(&line);
         }
 
-        doc
+        write!(f, "{doc}")
     }
 }
 
-impl Into<String> for SnippetsDoc {
+impl From<SnippetsDoc> for String {
     /// Converts the documentation object to string
-    fn into(self) -> String {
-        self.to_string()
+    fn from(doc: SnippetsDoc) -> Self {
+        doc.to_string()
     }
 }
@@ -0,0 +1,28 @@
+use super::Snippet;
+
+/// The snippets order in the generated snippets file && documentation table
+/// * Insertion - the order in which the snippets were added
+/// * Prefix - sorted by the snippet main prefix
+/// * Name - sorted by the snippet name
+/// * Custom - sorted by the custom key
+#[derive(Debug, Clone, Copy, Default)]
+pub enum SnippetsOrder {
+    #[default]
+    Insertion,
+    Prefix,
+    Name,
+    Custom(fn(&Snippet) -> String),
+}
+
+impl SnippetsOrder {
+    /// Sorts the snippets list (the sort is stable, so the equal keys keep the insertion order)
+    /// * snippets - the snippets list, where <(SNIPPET_KEY, SNIPPET)>
+    pub fn sort<K>(&self, snippets: &mut [(K, &Snippet)]) {
+        match self {
+            Self::Insertion => {}
+            Self::Prefix => snippets.sort_by(|(_, a), (_, b)| a.prefix().cmp(b.prefix())),
+            Self::Name => snippets.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name)),
+            Self::Custom(key) => snippets.sort_by_cached_key(|(_, s)| key(s)),
+        }
+    }
+}
@@ -0,0 +1,426 @@
+use super::{ SnippetVariable, SnippetTransform, FormatItem, CaseModifier };
+use std::{ iter::Peekable, str::Chars };
+
+/// The snippet body syntax tree node (see the VS Code snippet grammar)
+/// * Text - the plain text
+/// * Tabstop - the tabstop `$1` / `${1}` (optionally with a transform `${1/regex/format/options}`)
+/// * Placeholder - the placeholder `${1:default}`
+/// * Choice - the choice list `${1|one,two,three|}`
+/// * Variable - the variable `$NAME` / `${NAME:default}` / `${NAME/regex/format/options}`
+/// * UnknownVariable - the variable which isn't in the [documented list](SnippetVariable) (VS Code inserts its name as placeholder)
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnippetNode {
+    Text(String),
+    Tabstop { index: u32, transform: Option<SnippetTransform> },
+    Placeholder { index: u32, value: Vec<SnippetNode> },
+    Choice { index: u32, options: Vec<String> },
+    Variable { name: SnippetVariable, default: Option<Vec<SnippetNode>>, transform: Option<SnippetTransform> },
+    UnknownVariable { name: String, default: Option<Vec<SnippetNode>>, transform: Option<SnippetTransform> },
+}
+
+impl SnippetNode {
+    /// Parses the snippet body text to syntax tree
+    /// * text - the snippet body text (the body lines joined by '\n')
+    pub fn parse(text: &str) -> std::result::Result<Vec<Self>, SyntaxError> {
+        Parser::new(text).parse()
+    }
+
+    /// Creates the variable node (the unknown variable names are kept as is)
+    /// * name - the variable name (example: "TM_FILENAME")
+    /// * default - the default value
+    /// * transform - the regex transform
+    pub fn variable(name: &str, default: Option<Vec<Self>>, transform: Option<SnippetTransform>) -> Self {
+        match name.parse() {
+            Ok(name) => Self::Variable { name, default, transform },
+            Err(_) => Self::UnknownVariable { name: name.into(), default, transform },
+        }
+    }
+}
+
+/// The snippet body syntax error
+/// * line - the line number in the snippet body (starts from 1)
+/// * column - the column number in the line (starts from 1)
+/// * kind - the error kind
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: SyntaxErrorKind,
+}
+
+/// The snippet body syntax error kind
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxErrorKind {
+    /// The '${' is opened, but never closed
+    UnclosedBrace,
+    /// The unexpected symbol in transform
+    UnexpectedChar(char),
+    /// The choice list is malformed
+    MalformedChoice(&'static str),
+    /// The regex transform is malformed
+    MalformedTransform(&'static str),
+    /// The transform regex can't be compiled (the regex error message)
+    InvalidRegex(String),
+}
+
+impl std::fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+
+        match &self.kind {
+            SyntaxErrorKind::UnclosedBrace => write!(f, "unclosed '${{'"),
+            SyntaxErrorKind::UnexpectedChar(c) => write!(f, "unexpected symbol '{c}'"),
+            SyntaxErrorKind::MalformedChoice(e) => write!(f, "malformed choice: {e}"),
+            SyntaxErrorKind::MalformedTransform(e) => write!(f, "malformed transform: {e}"),
+            SyntaxErrorKind::InvalidRegex(e) => write!(f, "invalid transform regex: {e}"),
+        }
+    }
+}
+
+
+/// The snippet body parser
+struct Parser<'a> {
+    text: &'a str,
+    chars: Peekable<Chars<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            chars: text.chars().peekable(),
+            pos: 0,
+        }
+    }
+
+    /// Parses the whole text
+    fn parse(mut self) -> std::result::Result<Vec<SnippetNode>, SyntaxError> {
+        self.parse_any(None)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c.is_some() { self.pos += 1; }
+        c
+    }
+
+    /// Creates an error at the symbol position
+    fn error(&self, pos: usize, kind: SyntaxErrorKind) -> SyntaxError {
+        let (mut line, mut column) = (1, 1);
+        for c in self.text.chars().take(pos) {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+
+        SyntaxError { line, column, kind }
+    }
+
+    /// Parses the nodes list until the end of text or the unescaped '}' (if `opened_at` is set),
+    /// the backslash which escapes nothing && the '${' which starts no tabstop or variable are kept as text (as in VS Code)
+    /// * opened_at - the position of the opened '${'
+    fn parse_any(&mut self, opened_at: Option<usize>) -> std::result::Result<Vec<SnippetNode>, SyntaxError> {
+        let mut nodes = vec![];
+        let mut text = String::new();
+
+        loop {
+            let pos = self.pos;
+            match self.peek() {
+                None => {
+                    if let Some(at) = opened_at {
+                        return Err(self.error(at, SyntaxErrorKind::UnclosedBrace));
+                    }
+                    break;
+                }
+                Some('}') if opened_at.is_some() => break,
+                Some('\\') => {
+                    self.next();
+                    match self.peek() {
+                        Some(c @ ('$' | '}' | '\\')) => { self.next(); text.push(c); }
+                        _ => text.push('\\'),
+                    }
+                }
+                Some('$') => {
+                    self.next();
+                    match self.peek() {
+                        Some(c) if c.is_ascii_digit() || c == '{' || c == '_' || c.is_ascii_alphabetic() => {
+                            let saved = (self.chars.clone(), self.pos);
+                            match self.parse_dollar(pos)? {
+                                Some(node) => {
+                                    if !text.is_empty() { nodes.push(SnippetNode::Text(std::mem::take(&mut text))); }
+                                    nodes.push(node);
+                                }
+                                None => {
+                                    // backtracking to the symbol after '$' (example: "${#arr[@]}"):
+                                    (self.chars, self.pos) = saved;
+                                    text.push('$');
+                                }
+                            }
+                        }
+                        _ => text.push('$'),
+                    }
+                }
+                Some(c) => { self.next(); text.push(c); }
+            }
+        }
+
+        if !text.is_empty() { nodes.push(SnippetNode::Text(text)); }
+        Ok(nodes)
+    }
+
+    /// Parses the construction after the '$' symbol (None if it's not a tabstop or variable)
+    /// * start - the position of '$'
+    fn parse_dollar(&mut self, start: usize) -> std::result::Result<Option<SnippetNode>, SyntaxError> {
+        match self.peek() {
+            Some('{') => {
+                self.next();
+                self.parse_braced(start)
+            }
+            Some(c) if c.is_ascii_digit() => Ok(Some(SnippetNode::Tabstop { index: self.parse_int(), transform: None })),
+            _ => Ok(Some(SnippetNode::variable(&self.parse_var_name(), None, None))),
+        }
+    }
+
+    /// Parses the construction after the '${' symbols (None if it's not a tabstop or variable)
+    /// * start - the position of '$'
+    fn parse_braced(&mut self, start: usize) -> std::result::Result<Option<SnippetNode>, SyntaxError> {
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => {
+                let index = self.parse_int();
+
+                match self.next() {
+                    Some('}') => Ok(Some(SnippetNode::Tabstop { index, transform: None })),
+                    Some(':') => {
+                        let value = self.parse_any(Some(start))?;
+                        self.next();
+                        Ok(Some(SnippetNode::Placeholder { index, value }))
+                    }
+                    Some('|') => {
+                        let options = self.parse_choice(start)?;
+                        Ok(Some(SnippetNode::Choice { index, options }))
+                    }
+                    Some('/') => {
+                        let transform = self.parse_transform(start)?;
+                        Ok(Some(SnippetNode::Tabstop { index, transform: Some(transform) }))
+                    }
+                    Some(_) => Ok(None),
+                    None => Err(self.error(start, SyntaxErrorKind::UnclosedBrace)),
+                }
+            }
+            Some(c) if c == '_' || c.is_ascii_alphabetic() => {
+                let name = self.parse_var_name();
+
+                match self.next() {
+                    Some('}') => Ok(Some(SnippetNode::variable(&name, None, None))),
+                    Some(':') => {
+                        let default = self.parse_any(Some(start))?;
+                        self.next();
+                        Ok(Some(SnippetNode::variable(&name, Some(default), None)))
+                    }
+                    Some('/') => {
+                        let transform = self.parse_transform(start)?;
+                        Ok(Some(SnippetNode::variable(&name, None, Some(transform))))
+                    }
+                    Some(_) => Ok(None),
+                    None => Err(self.error(start, SyntaxErrorKind::UnclosedBrace)),
+                }
+            }
+            Some(_) => Ok(None),
+            None => Err(self.error(start, SyntaxErrorKind::UnclosedBrace)),
+        }
+    }
+
+    /// Parses the choice options after '${N|' (including the closing '|}'),
+    /// the backslash which escapes nothing is kept as text
+    /// * start - the position of '$'
+    fn parse_choice(&mut self, start: usize) -> std::result::Result<Vec<String>, SyntaxError> {
+        let mut options = vec![];
+        let mut option = String::new();
+
+        loop {
+            let pos = self.pos;
+            match self.next() {
+                None => return Err(self.error(start, SyntaxErrorKind::MalformedChoice("the choice list is not closed by '|}'"))),
+                Some('\\') => match self.peek() {
+                    Some(c @ (',' | '|' | '\\')) => { self.next(); option.push(c); }
+                    _ => option.push('\\'),
+                },
+                Some(c @ (',' | '|')) => {
+                    if option.is_empty() {
+                        return Err(self.error(pos, SyntaxErrorKind::MalformedChoice("empty choice option")));
+                    }
+                    options.push(std::mem::take(&mut option));
+
+                    if c == '|' {
+                        return match self.next() {
+                            Some('}') => Ok(options),
+                            _ => Err(self.error(pos, SyntaxErrorKind::MalformedChoice("expected '}' after '|'"))),
+                        };
+                    }
+                }
+                Some(c) => option.push(c),
+            }
+        }
+    }
+
+    /// Parses the transform after '${N/' or '${NAME/' (including the closing '}')
+    /// * start - the position of '$'
+    fn parse_transform(&mut self, start: usize) -> std::result::Result<SnippetTransform, SyntaxError> {
+        // parsing regex:
+        let mut regex = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error(start, SyntaxErrorKind::MalformedTransform("the regex is not closed by '/'"))),
+                Some('/') => break,
+                Some('\\') => match self.next() {
+                    Some('/') => regex.push('/'),
+                    Some(c) => { regex.push('\\'); regex.push(c); }
+                    None => return Err(self.error(start, SyntaxErrorKind::MalformedTransform("the regex is not closed by '/'"))),
+                },
+                Some(c) => regex.push(c),
+            }
+        }
+
+        // parsing format string:
+        let mut format = vec![];
+        let mut text = String::new();
+        loop {
+            let pos = self.pos;
+            match self.next() {
+                None => return Err(self.error(start, SyntaxErrorKind::MalformedTransform("the format is not closed by '/'"))),
+                Some('/') => break,
+                Some('\\') => match self.peek() {
+                    Some(c @ ('$' | '/' | '\\')) => { self.next(); text.push(c); }
+                    _ => text.push('\\'),
+                },
+                Some('$') => match self.peek() {
+                    Some(c) if c.is_ascii_digit() || c == '{' => {
+                        if !text.is_empty() { format.push(FormatItem::Text(std::mem::take(&mut text))); }
+                        format.push(self.parse_format_item(pos)?);
+                    }
+                    _ => text.push('$'),
+                },
+                Some(c) => text.push(c),
+            }
+        }
+        if !text.is_empty() { format.push(FormatItem::Text(text)); }
+
+        // parsing options:
+        let mut options = String::new();
+        loop {
+            let pos = self.pos;
+            match self.next() {
+                None => return Err(self.error(start, SyntaxErrorKind::UnclosedBrace)),
+                Some('}') => break,
+                Some(c) if SnippetTransform::OPTIONS.contains(c) => options.push(c),
+                Some(c) => return Err(self.error(pos, SyntaxErrorKind::UnexpectedChar(c))),
+            }
+        }
+
+        let transform = SnippetTransform { regex, format, options };
+        transform.compile().map_err(|e| self.error(start, SyntaxErrorKind::InvalidRegex(e)))?;
+
+        Ok(transform)
+    }
+
+    /// Parses the format item after '$' in transform format string
+    /// * start - the position of '$'
+    fn parse_format_item(&mut self, start: usize) -> std::result::Result<FormatItem, SyntaxError> {
+        if self.peek() != Some('{') {
+            return Ok(FormatItem::Group(self.parse_int()));
+        }
+        self.next();
+
+        let pos = self.pos;
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error(pos, SyntaxErrorKind::MalformedTransform("expected group number after '${'")));
+        }
+        let group = self.parse_int();
+
+        let pos = self.pos;
+        match self.next() {
+            Some('}') => Ok(FormatItem::Group(group)),
+            Some(':') => match self.peek() {
+                Some('/') => {
+                    self.next();
+                    let name = self.parse_var_name();
+                    match (name.parse::<CaseModifier>(), self.next()) {
+                        (Ok(modifier), Some('}')) => Ok(FormatItem::Case(group, modifier)),
+                        _ => Err(self.error(pos, SyntaxErrorKind::MalformedTransform("unknown case modifier"))),
+                    }
+                }
+                Some('+') => {
+                    self.next();
+                    let if_value = self.parse_format_text(start, false)?;
+                    Ok(FormatItem::Conditional { group, if_value: Some(if_value), else_value: None })
+                }
+                Some('?') => {
+                    self.next();
+                    let if_value = self.parse_format_text(start, true)?;
+                    let else_value = self.parse_format_text(start, false)?;
+                    Ok(FormatItem::Conditional { group, if_value: Some(if_value), else_value: Some(else_value) })
+                }
+                Some('-') => {
+                    self.next();
+                    let else_value = self.parse_format_text(start, false)?;
+                    Ok(FormatItem::Conditional { group, if_value: None, else_value: Some(else_value) })
+                }
+                _ => {
+                    let else_value = self.parse_format_text(start, false)?;
+                    Ok(FormatItem::Conditional { group, if_value: None, else_value: Some(else_value) })
+                }
+            },
+            Some(c) => Err(self.error(pos, SyntaxErrorKind::UnexpectedChar(c))),
+            None => Err(self.error(start, SyntaxErrorKind::UnclosedBrace)),
+        }
+    }
+
+    /// Parses the conditional text of format item until the '}' (or ':' if `until_colon`)
+    /// * start - the position of '$'
+    /// * until_colon - stop on the unescaped ':'
+    fn parse_format_text(&mut self, start: usize, until_colon: bool) -> std::result::Result<String, SyntaxError> {
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error(start, SyntaxErrorKind::UnclosedBrace)),
+                Some('}') if !until_colon => return Ok(text),
+                Some(':') if until_colon => return Ok(text),
+                Some('\\') => match self.peek() {
+                    Some(c @ ('}' | ':' | '\\')) => { self.next(); text.push(c); }
+                    _ => text.push('\\'),
+                },
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    /// Parses the unsigned integer
+    fn parse_int(&mut self) -> u32 {
+        let mut value: u32 = 0;
+        while let Some(c) = self.peek() {
+            let Some(digit) = c.to_digit(10) else { break };
+            value = value.saturating_mul(10).saturating_add(digit);
+            self.next();
+        }
+        value
+    }
+
+    /// Parses the variable name `[_a-zA-Z][_a-zA-Z0-9]*`
+    fn parse_var_name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if c != '_' && !c.is_ascii_alphanumeric() { break; }
+            name.push(c);
+            self.next();
+        }
+        name
+    }
+}
@@ -0,0 +1,242 @@
+use fancy_regex::Regex;
+
+/// The regex transform `/regex/format/options`
+/// * regex - the regular expression
+/// * format - the format string items
+/// * options - the regular expression options (example: "gi")
+///
+/// Example: `SnippetTransform::new("(.*)\\..+$").case(1, CaseModifier::Upcase)` is `/(.*)\..+$/${1:/upcase}/`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnippetTransform {
+    pub regex: String,
+    pub format: Vec<FormatItem>,
+    pub options: String,
+}
+
+impl SnippetTransform {
+    /// The supported regex options:
+    /// * g - replace all matches (instead of the first one)
+    /// * i - case insensitive matching
+    /// * m - multi-line mode ('^' && '$' match the line start && end)
+    /// * s - the '.' matches the new line
+    /// * u - unicode mode (always on)
+    pub const OPTIONS: &'static str = "gimsu";
+
+    /// Creates a new transform with empty format string
+    /// * regex - the regular expression
+    pub fn new<S>(regex: S) -> Self
+    where S: Into<String> {
+        Self {
+            regex: regex.into(),
+            format: vec![],
+            options: String::new(),
+        }
+    }
+
+    /// Appends the plain text to format string
+    /// * text - the plain text
+    pub fn text<S>(mut self, text: S) -> Self
+    where S: Into<String> {
+        let text = text.into();
+        match self.format.last_mut() {
+            Some(FormatItem::Text(last)) => last.push_str(&text),
+            _ => self.format.push(FormatItem::Text(text)),
+        }
+        self
+    }
+
+    /// Appends the capture group `${N}` to format string
+    /// * group - the capture group number
+    pub fn group(mut self, group: u32) -> Self {
+        self.format.push(FormatItem::Group(group));
+        self
+    }
+
+    /// Appends the capture group with case modifier `${N:/upcase}` to format string
+    /// * group - the capture group number
+    /// * modifier - the case modifier
+    pub fn case(mut self, group: u32, modifier: CaseModifier) -> Self {
+        self.format.push(FormatItem::Case(group, modifier));
+        self
+    }
+
+    /// Appends the conditional insertion to format string
+    /// * group - the capture group number
+    /// * if_value - the text inserted if the group is matched
+    /// * else_value - the text inserted if the group isn't matched
+    pub fn conditional(mut self, group: u32, if_value: Option<&str>, else_value: Option<&str>) -> Self {
+        self.format.push(FormatItem::Conditional {
+            group,
+            if_value: if_value.map(String::from),
+            else_value: else_value.map(String::from),
+        });
+        self
+    }
+
+    /// Sets the regex options && returns 'Self'
+    /// * options - the options (see [SnippetTransform::OPTIONS])
+    pub fn options<S>(mut self, options: S) -> Self
+    where S: Into<String> {
+        self.options = options.into();
+        self
+    }
+
+    /// Compiles the transform regex with options, returns the error message
+    /// (by 'fancy_regex', so the JavaScript look-behinds && back-references are supported)
+    pub fn compile(&self) -> std::result::Result<Regex, String> {
+        let flags = self.options.chars().filter(|c| "ims".contains(*c)).collect::<String>();
+        let regex = match flags.is_empty() {
+            true => Regex::new(&self.regex),
+            false => Regex::new(&format!("(?{flags}){}", self.regex)),
+        };
+        regex.map_err(|e| e.to_string())
+    }
+
+    /// Applies the transform to the value (the text outside of matches is kept as is), returns the error message
+    /// * value - the variable or tabstop value
+    pub fn apply(&self, value: &str) -> std::result::Result<String, String> {
+        let regex = self.compile()?;
+        let limit = if self.options.contains('g') { 0 } else { 1 };
+
+        let result = regex.try_replacen(value, limit, |caps: &fancy_regex::Captures| {
+            let group = |n: &u32| caps.get(*n as usize).map(|m| m.as_str()).unwrap_or_default();
+
+            self.format
+                .iter()
+                .map(|item| match item {
+                    FormatItem::Text(text) => text.clone(),
+                    FormatItem::Group(n) => group(n).to_owned(),
+                    FormatItem::Case(n, modifier) => modifier.apply(group(n)),
+                    FormatItem::Conditional { group: n, if_value, else_value } => {
+                        let value = if group(n).is_empty() { else_value } else { if_value };
+                        value.clone().unwrap_or_default()
+                    }
+                })
+                .collect::<String>()
+        }).map_err(|e| e.to_string())?;
+
+        Ok(result.into_owned())
+    }
+}
+
+impl std::fmt::Display for SnippetTransform {
+    /// Writes the transform syntax `/regex/format/options` (without the '${N' prefix && the '}' suffix)
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "/{}/", self.regex.replace('/', "\\/"))?;
+        for item in &self.format {
+            write!(f, "{item}")?;
+        }
+        write!(f, "/{}", self.options)
+    }
+}
+
+
+/// The transform format string item
+/// * Text - the plain text
+/// * Group - the regex capture group `$1` / `${1}`
+/// * Case - the capture group with case modifier `${1:/upcase}`
+/// * Conditional - the conditional insertion `${1:+if}` / `${1:?if:else}` / `${1:-else}` / `${1:else}`
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatItem {
+    Text(String),
+    Group(u32),
+    Case(u32, CaseModifier),
+    Conditional { group: u32, if_value: Option<String>, else_value: Option<String> },
+}
+
+impl std::fmt::Display for FormatItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => write!(f, "{}", escape(text, &['$', '/', '\\'])),
+            Self::Group(n) => write!(f, "${{{n}}}"),
+            Self::Case(n, modifier) => write!(f, "${{{n}:/{}}}", modifier.as_str()),
+            Self::Conditional { group, if_value, else_value } => {
+                let specials = ['}', ':', '\\'];
+                match (if_value, else_value) {
+                    (Some(if_value), Some(else_value)) => {
+                        write!(f, "${{{group}:?{}:{}}}", escape(if_value, &specials), escape(else_value, &specials))
+                    }
+                    (Some(if_value), None) => write!(f, "${{{group}:+{}}}", escape(if_value, &specials)),
+                    (None, else_value) => write!(f, "${{{group}:-{}}}", escape(else_value.as_deref().unwrap_or_default(), &specials)),
+                }
+            }
+        }
+    }
+}
+
+
+/// The case modifier of transform format group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseModifier {
+    Upcase,
+    Downcase,
+    Capitalize,
+    Camelcase,
+    Pascalcase,
+}
+
+impl CaseModifier {
+    /// Get the modifier name (without slash)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Upcase => "upcase",
+            Self::Downcase => "downcase",
+            Self::Capitalize => "capitalize",
+            Self::Camelcase => "camelcase",
+            Self::Pascalcase => "pascalcase",
+        }
+    }
+
+    /// Applies the case modifier to the text (like VS Code does)
+    /// * text - the captured text (example: "user_profile")
+    pub fn apply(&self, text: &str) -> String {
+        let capitalize = |word: &str| {
+            let mut chars = word.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        };
+        let words = || text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty());
+
+        match self {
+            Self::Upcase => text.to_uppercase(),
+            Self::Downcase => text.to_lowercase(),
+            Self::Capitalize => capitalize(text),
+            Self::Pascalcase => words().map(capitalize).collect(),
+            Self::Camelcase => words()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i > 0 { return capitalize(word); }
+                    let mut chars = word.chars();
+                    chars.next().map(|c| c.to_lowercase().chain(chars).collect()).unwrap_or_default()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl std::str::FromStr for CaseModifier {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "upcase" => Ok(Self::Upcase),
+            "downcase" => Ok(Self::Downcase),
+            "capitalize" => Ok(Self::Capitalize),
+            "camelcase" => Ok(Self::Camelcase),
+            "pascalcase" => Ok(Self::Pascalcase),
+            _ => Err(()),
+        }
+    }
+}
+
+
+/// Escapes the special symbols of format string by backslash
+/// * text - the plain text
+/// * specials - the symbols to escape
+fn escape(text: &str, specials: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if specials.contains(&c) { escaped.push('\\'); }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,132 @@
+/// The VS Code snippet variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SnippetVariable {
+    // the editor state:
+    TmSelectedText,
+    TmCurrentLine,
+    TmCurrentWord,
+    TmLineIndex,
+    TmLineNumber,
+    TmFilename,
+    TmFilenameBase,
+    TmDirectory,
+    TmDirectoryBase,
+    TmFilepath,
+    RelativeFilepath,
+    Clipboard,
+    WorkspaceName,
+    WorkspaceFolder,
+    CursorIndex,
+    CursorNumber,
+
+    // the current date && time:
+    CurrentYear,
+    CurrentYearShort,
+    CurrentMonth,
+    CurrentMonthName,
+    CurrentMonthNameShort,
+    CurrentDate,
+    CurrentDayName,
+    CurrentDayNameShort,
+    CurrentHour,
+    CurrentMinute,
+    CurrentSecond,
+    CurrentSecondsUnix,
+    CurrentTimezoneOffset,
+
+    // the random values:
+    Random,
+    RandomHex,
+    Uuid,
+
+    // the language comments:
+    BlockCommentStart,
+    BlockCommentEnd,
+    LineComment,
+}
+
+impl SnippetVariable {
+    /// The all documented variables
+    pub const ALL: [Self; 35] = [
+        Self::TmSelectedText, Self::TmCurrentLine, Self::TmCurrentWord, Self::TmLineIndex, Self::TmLineNumber,
+        Self::TmFilename, Self::TmFilenameBase, Self::TmDirectory, Self::TmDirectoryBase, Self::TmFilepath,
+        Self::RelativeFilepath, Self::Clipboard, Self::WorkspaceName, Self::WorkspaceFolder, Self::CursorIndex,
+        Self::CursorNumber, Self::CurrentYear, Self::CurrentYearShort, Self::CurrentMonth, Self::CurrentMonthName,
+        Self::CurrentMonthNameShort, Self::CurrentDate, Self::CurrentDayName, Self::CurrentDayNameShort,
+        Self::CurrentHour, Self::CurrentMinute, Self::CurrentSecond, Self::CurrentSecondsUnix,
+        Self::CurrentTimezoneOffset, Self::Random, Self::RandomHex, Self::Uuid, Self::BlockCommentStart,
+        Self::BlockCommentEnd, Self::LineComment,
+    ];
+
+    /// Get the variable name (example: "TM_FILENAME")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TmSelectedText => "TM_SELECTED_TEXT",
+            Self::TmCurrentLine => "TM_CURRENT_LINE",
+            Self::TmCurrentWord => "TM_CURRENT_WORD",
+            Self::TmLineIndex => "TM_LINE_INDEX",
+            Self::TmLineNumber => "TM_LINE_NUMBER",
+            Self::TmFilename => "TM_FILENAME",
+            Self::TmFilenameBase => "TM_FILENAME_BASE",
+            Self::TmDirectory => "TM_DIRECTORY",
+            Self::TmDirectoryBase => "TM_DIRECTORY_BASE",
+            Self::TmFilepath => "TM_FILEPATH",
+            Self::RelativeFilepath => "RELATIVE_FILEPATH",
+            Self::Clipboard => "CLIPBOARD",
+            Self::WorkspaceName => "WORKSPACE_NAME",
+            Self::WorkspaceFolder => "WORKSPACE_FOLDER",
+            Self::CursorIndex => "CURSOR_INDEX",
+            Self::CursorNumber => "CURSOR_NUMBER",
+            Self::CurrentYear => "CURRENT_YEAR",
+            Self::CurrentYearShort => "CURRENT_YEAR_SHORT",
+            Self::CurrentMonth => "CURRENT_MONTH",
+            Self::CurrentMonthName => "CURRENT_MONTH_NAME",
+            Self::CurrentMonthNameShort => "CURRENT_MONTH_NAME_SHORT",
+            Self::CurrentDate => "CURRENT_DATE",
+            Self::CurrentDayName => "CURRENT_DAY_NAME",
+            Self::CurrentDayNameShort => "CURRENT_DAY_NAME_SHORT",
+            Self::CurrentHour => "CURRENT_HOUR",
+            Self::CurrentMinute => "CURRENT_MINUTE",
+            Self::CurrentSecond => "CURRENT_SECOND",
+            Self::CurrentSecondsUnix => "CURRENT_SECONDS_UNIX",
+            Self::CurrentTimezoneOffset => "CURRENT_TIMEZONE_OFFSET",
+            Self::Random => "RANDOM",
+            Self::RandomHex => "RANDOM_HEX",
+            Self::Uuid => "UUID",
+            Self::BlockCommentStart => "BLOCK_COMMENT_START",
+            Self::BlockCommentEnd => "BLOCK_COMMENT_END",
+            Self::LineComment => "LINE_COMMENT",
+        }
+    }
+}
+
+impl std::str::FromStr for SnippetVariable {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(())
+    }
+}
+
+impl std::fmt::Display for SnippetVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl AsRef<str> for SnippetVariable {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+
+/// The snippet variables resolver (the source of variable values for documentation, previews && simulation)
+pub trait VariableResolver {
+    /// Resolves the variable value (returns 'None' if the value is unknown, then the variable default is used)
+    /// * var - the snippet variable
+    fn resolve(&self, var: SnippetVariable) -> Option<String>;
+}
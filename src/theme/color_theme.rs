@@ -0,0 +1,276 @@
+use crate::prelude::*;
+use super::{ TokenColor, SemanticTokenStyle, ThemeDoc };
+use serde::{ Serialize, Deserialize };
+use std::path::{ Path, PathBuf };
+
+/// The color theme type (the theme files without 'type' are dark, unless the contribution's 'uiTheme' says otherwise)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeKind {
+    #[serde(rename = "light")]
+    Light,
+    #[default]
+    #[serde(rename = "dark")]
+    Dark,
+    #[serde(rename = "hc")]
+    HighContrast,
+    #[serde(rename = "hcLight")]
+    HighContrastLight,
+}
+
+impl ThemeKind {
+    /// Get the base theme name used by 'contributes.themes' (example: "vs-dark")
+    pub fn ui_theme(&self) -> &'static str {
+        match self {
+            Self::Light => "vs",
+            Self::Dark => "vs-dark",
+            Self::HighContrast => "hc-black",
+            Self::HighContrastLight => "hc-light",
+        }
+    }
+
+    /// Get the theme type from base theme name (example: "vs-dark")
+    pub fn from_ui_theme(ui_theme: &str) -> Option<Self> {
+        match ui_theme {
+            "vs" => Some(Self::Light),
+            "vs-dark" => Some(Self::Dark),
+            "hc-black" => Some(Self::HighContrast),
+            "hc-light" => Some(Self::HighContrastLight),
+            _ => None,
+        }
+    }
+
+    /// Get the theme type title
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+            Self::HighContrast => "High Contrast",
+            Self::HighContrastLight => "High Contrast Light",
+        }
+    }
+}
+
+impl std::fmt::Display for ThemeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+
+/// The color theme
+/// * name - the theme name (the label in theme picker)
+/// * kind - the theme [type](ThemeKind)
+/// * file_name - the theme file name
+/// * semantic_highlighting - the semantic highlighting flag
+/// * colors - the workbench colors in insertion order, where <(COLOR_ID, COLOR)>
+/// * token_colors - the TextMate [token rules](TokenColor)
+/// * semantic_token_colors - the semantic token styles in insertion order, where <(SELECTOR, STYLE)>
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorTheme {
+    #[serde(rename = "$schema", default = "ColorTheme::schema")]
+    schema: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: ThemeKind,
+    #[serde(skip)]
+    pub file_name: PathBuf,
+    #[serde(rename = "semanticHighlighting", skip_serializing_if = "Option::is_none", default)]
+    pub semantic_highlighting: Option<bool>,
+    #[serde(skip_serializing_if = "OrderedMap::is_empty", default)]
+    colors: OrderedMap<String, String>,
+    #[serde(rename = "tokenColors", skip_serializing_if = "Vec::is_empty", default)]
+    token_colors: Vec<TokenColor>,
+    #[serde(rename = "semanticTokenColors", skip_serializing_if = "OrderedMap::is_empty", default)]
+    semantic_token_colors: OrderedMap<String, SemanticTokenStyle>,
+}
+
+impl ColorTheme {
+    /// The font styles allowed in token rules
+    pub const FONT_STYLES: [&'static str; 4] = ["italic", "bold", "underline", "strikethrough"];
+
+    /// Creates a new color theme
+    /// * name - the theme name
+    /// * kind - the theme type
+    pub fn new<S>(name: S, kind: ThemeKind) -> Self
+    where S: Into<String>
+    {
+        let name = name.into();
+        let file_name = format!("{}-color-theme.json", to_latin_text(&name.to_lowercase(), true)).into();
+
+        Self {
+            schema: Self::schema(),
+            name,
+            kind,
+            file_name,
+            semantic_highlighting: None,
+            colors: OrderedMap::default(),
+            token_colors: vec![],
+            semantic_token_colors: OrderedMap::default(),
+        }
+    }
+
+    /// The theme JSON schema URL
+    fn schema() -> String {
+        "vscode://schemas/color-theme".into()
+    }
+
+    /// Sets the theme file name && returns 'Self'
+    /// * file_name - the theme file name
+    pub fn set_file_name<P>(mut self, file_name: P) -> Self
+    where P: Into<PathBuf> {
+        self.file_name = file_name.into();
+        self
+    }
+
+    /// Sets the semantic highlighting flag && returns 'Self'
+    pub fn set_semantic_highlighting(mut self, value: bool) -> Self {
+        self.semantic_highlighting = Some(value);
+        self
+    }
+
+    /// Sets the workbench color && returns 'Self' (the existing color is replaced)
+    /// * id - the color id (example: "editor.background")
+    /// * color - the hex color (example: "#1E1E1E")
+    pub fn set_color<S>(mut self, id: S, color: S) -> Self
+    where S: Into<String>
+    {
+        let (id, color) = (id.into(), color.into());
+        match self.colors.0.iter_mut().find(|(k, _)| *k == id) {
+            Some((_, value)) => *value = color,
+            None => self.colors.0.push((id, color)),
+        }
+        self
+    }
+
+    /// Adds the TextMate token rule && returns 'Self'
+    /// * rule - the token rule
+    pub fn add_token_color(mut self, rule: TokenColor) -> Self {
+        self.token_colors.push(rule);
+        self
+    }
+
+    /// Sets the semantic token style && returns 'Self' (the existing style is replaced)
+    /// * selector - the semantic token selector (example: "variable.readonly")
+    /// * style - the token style
+    pub fn set_semantic_color<S>(mut self, selector: S, style: SemanticTokenStyle) -> Self
+    where S: Into<String>
+    {
+        let selector = selector.into();
+        match self.semantic_token_colors.0.iter_mut().find(|(k, _)| *k == selector) {
+            Some((_, value)) => *value = style,
+            None => self.semantic_token_colors.0.push((selector, style)),
+        }
+        self
+    }
+
+    /// Get the workbench color by its id
+    pub fn color(&self, id: &str) -> Option<&str> {
+        self.colors.0.iter().find(|(k, _)| k == id).map(|(_, v)| v.as_str())
+    }
+
+    /// Get the workbench colors in insertion order, where <(COLOR_ID, COLOR)>
+    pub fn colors(&self) -> &[(String, String)] {
+        &self.colors.0
+    }
+
+    /// Get the TextMate token rules
+    pub fn token_colors(&self) -> &[TokenColor] {
+        &self.token_colors
+    }
+
+    /// Get the semantic token styles in insertion order, where <(SELECTOR, STYLE)>
+    pub fn semantic_token_colors(&self) -> &[(String, SemanticTokenStyle)] {
+        &self.semantic_token_colors.0
+    }
+
+    /// Check the color is hex color: "#RGB", "#RGBA", "#RRGGBB" or "#RRGGBBAA"
+    pub fn is_color(color: &str) -> bool {
+        match color.strip_prefix('#') {
+            Some(hex) => [3, 4, 6, 8].contains(&hex.len()) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            None => false,
+        }
+    }
+
+    /// Checks the theme colors && font styles
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |error: String| Err(Error::InvalidTheme { name: self.name.clone(), error });
+        let check_color = |place: &str, color: &Option<String>| match color {
+            Some(color) if !Self::is_color(color) => invalid(format!("the {place} color '{color}' isn't hex color")),
+            _ => Ok(()),
+        };
+
+        for (id, color) in &self.colors.0 {
+            check_color(id, &Some(color.clone()))?;
+        }
+
+        for rule in &self.token_colors {
+            let place = rule.scope.join(", ");
+            check_color(&place, &rule.settings.foreground)?;
+            check_color(&place, &rule.settings.background)?;
+
+            let styles = rule.settings.font_style.as_deref().unwrap_or_default();
+            if let Some(style) = styles.split_whitespace().find(|s| !Self::FONT_STYLES.contains(s)) {
+                return invalid(format!("the {place} font style '{style}' is unknown"));
+            }
+        }
+
+        for (selector, style) in &self.semantic_token_colors.0 {
+            check_color(selector, &style.foreground)?;
+        }
+
+        Ok(())
+    }
+
+    /// Generates the theme documentation
+    pub fn documentation(&self) -> ThemeDoc {
+        let mut doc = ThemeDoc::new(&self.name, self.kind);
+
+        for (id, color) in &self.colors.0 {
+            doc.add_color(id, color);
+        }
+
+        for rule in &self.token_colors {
+            let settings = &rule.settings;
+            let style = [&settings.foreground, &settings.background, &settings.font_style]
+                .into_iter()
+                .flatten()
+                .filter(|s| !s.is_empty())
+                .cloned()
+                .collect::<Vec<_>>();
+            doc.add_token(rule.scope.join(", "), style.join(" "));
+        }
+
+        doc
+    }
+
+    /// Converts the theme to JSON string
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self).map_err(Error::from)
+    }
+
+    /// Imports the theme from VS Code theme JSON (the comments && trailing commas are allowed)
+    /// * json - the theme file contents
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut this: Self = serde_json::from_str(&strip_json_comments(json))?;
+        this.file_name = Self::new(this.name.as_str(), this.kind).file_name;
+        Ok(this)
+    }
+
+    /// Writes the theme to file "%DIR/themes/%FILE_NAME"
+    /// * dir - the package root directory path (without '/themes' folder)
+    pub fn write_to<P>(&self, dir: P) -> Result<ThemeDoc>
+    where P: Into<PathBuf>
+    {
+        self.write_into(&mut crate::FsSink::new(dir))
+    }
+
+    /// Writes the theme file "themes/<FILE_NAME>" to output && returns the documentation
+    /// * sink - the package output
+    pub fn write_into(&self, sink: &mut dyn crate::OutputSink) -> Result<ThemeDoc> {
+        let path = Path::new("themes").join(&self.file_name);
+        sink.write_file(&path, self.to_json()?.as_bytes())?;
+
+        Ok(self.documentation())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod token_color;  pub use token_color::*;
+pub mod color_theme;  pub use color_theme::*;
+pub mod theme_doc;    pub use theme_doc::*;
@@ -0,0 +1,83 @@
+use crate::tools::markdown_table;
+use super::ThemeKind;
+
+/// The color theme documentation generator
+/// * title - the theme name
+/// * kind - the theme type
+/// * colors - the workbench colors, where '<(COLOR_ID, COLOR)>'
+/// * tokens - the token rules, where '<(SCOPES, STYLE)>'
+#[derive(Debug, Clone)]
+pub struct ThemeDoc {
+    pub title: String,
+    pub kind: ThemeKind,
+    pub colors: Vec<(String, String)>,
+    pub tokens: Vec<(String, String)>,
+}
+
+impl ThemeDoc {
+    /// Creates a new 'ThemeDoc' object
+    /// * title - the theme name
+    /// * kind - the theme type
+    pub fn new<S>(title: S, kind: ThemeKind) -> Self
+    where S: Into<String>
+    {
+        Self {
+            title: title.into(),
+            kind,
+            colors: vec![],
+            tokens: vec![],
+        }
+    }
+
+    /// Adds the workbench color to preview
+    /// * id - the color id
+    /// * color - the hex color
+    pub fn add_color<S>(&mut self, id: S, color: S)
+    where S: Into<String>
+    {
+        self.colors.push((id.into(), color.into()));
+    }
+
+    /// Adds the token rule to preview
+    /// * scopes - the token scopes
+    /// * style - the token style (example: "#6A9955 italic")
+    pub fn add_token<S>(&mut self, scopes: S, style: S)
+    where S: Into<String>
+    {
+        self.tokens.push((scopes.into(), style.into()));
+    }
+}
+
+impl std::fmt::Display for ThemeDoc {
+    /// Converts the documentation object to string
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut doc = format!("# {title} [{kind} theme]:\n", title = &self.title, kind = &self.kind);
+
+        if !self.colors.is_empty() {
+            let rows = self.colors
+                .iter()
+                .map(|(id, color)| vec![format!("`{id}`"), format!("`{color}`")])
+                .collect::<Vec<_>>();
+            doc.push_str(&markdown_table(&["Color:", "Value:"], &rows));
+        }
+
+        if !self.tokens.is_empty() {
+            if !self.colors.is_empty() { doc.push('\n'); }
+
+            let rows = self.tokens
+                .iter()
+                .map(|(scopes, style)| vec![scopes.clone(), format!("`{style}`")])
+                .collect::<Vec<_>>();
+            doc.push_str(&markdown_table(&["Scope:", "Style:"], &rows));
+        }
+
+        write!(f, "{doc}")
+    }
+}
+
+impl From<ThemeDoc> for String {
+    /// Converts the documentation object to string
+    fn from(doc: ThemeDoc) -> Self {
+        doc.to_string()
+    }
+}
@@ -0,0 +1,200 @@
+use serde::{ Serialize, Deserialize };
+
+/// The TextMate token color rule
+/// * name - the rule description
+/// * scope - the TextMate scope selectors (example: ["comment", "string.quoted"])
+/// * settings - the token style
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenColor {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name: Option<String>,
+    #[serde(serialize_with = "ser_scope", deserialize_with = "de_scope", skip_serializing_if = "Vec::is_empty", default)]
+    pub scope: Vec<String>,
+    pub settings: TokenSettings,
+}
+
+impl TokenColor {
+    /// Creates a new token color rule
+    /// * scope - the TextMate scope selectors (comma separated, example: "comment, string.quoted")
+    pub fn new(scope: &str) -> Self {
+        Self {
+            name: None,
+            scope: crate::tools::split_languages(scope),
+            settings: TokenSettings::default(),
+        }
+    }
+
+    /// Sets the rule description && returns 'Self'
+    pub fn set_name<S>(mut self, name: S) -> Self
+    where S: Into<String> {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the foreground color && returns 'Self'
+    /// * color - the hex color (example: "#6A9955")
+    pub fn foreground<S>(mut self, color: S) -> Self
+    where S: Into<String> {
+        self.settings.foreground = Some(color.into());
+        self
+    }
+
+    /// Sets the background color && returns 'Self'
+    /// * color - the hex color (example: "#1E1E1E")
+    pub fn background<S>(mut self, color: S) -> Self
+    where S: Into<String> {
+        self.settings.background = Some(color.into());
+        self
+    }
+
+    /// Sets the font style && returns 'Self'
+    /// * style - the space separated styles: "italic", "bold", "underline", "strikethrough" (the empty string resets style)
+    pub fn font_style<S>(mut self, style: S) -> Self
+    where S: Into<String> {
+        self.settings.font_style = Some(style.into());
+        self
+    }
+}
+
+/// The token style
+/// * foreground - the foreground color
+/// * background - the background color
+/// * font_style - the font style (example: "italic bold")
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenSettings {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub foreground: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub background: Option<String>,
+    #[serde(rename = "fontStyle", skip_serializing_if = "Option::is_none", default)]
+    pub font_style: Option<String>,
+}
+
+
+/// The semantic token style (written as color string if only the foreground is set)
+/// * foreground - the foreground color
+/// * bold, italic, underline, strikethrough - the font style flags
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SemanticTokenStyle {
+    pub foreground: Option<String>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underline: Option<bool>,
+    pub strikethrough: Option<bool>,
+}
+
+impl SemanticTokenStyle {
+    /// Creates the semantic token style with foreground color
+    /// * color - the hex color (example: "#4EC9B0")
+    pub fn color<S>(color: S) -> Self
+    where S: Into<String> {
+        Self { foreground: Some(color.into()), ..Self::default() }
+    }
+
+    /// Sets the bold flag && returns 'Self'
+    pub fn bold(mut self, value: bool) -> Self {
+        self.bold = Some(value);
+        self
+    }
+
+    /// Sets the italic flag && returns 'Self'
+    pub fn italic(mut self, value: bool) -> Self {
+        self.italic = Some(value);
+        self
+    }
+
+    /// Sets the underline flag && returns 'Self'
+    pub fn underline(mut self, value: bool) -> Self {
+        self.underline = Some(value);
+        self
+    }
+
+    /// Sets the strikethrough flag && returns 'Self'
+    pub fn strikethrough(mut self, value: bool) -> Self {
+        self.strikethrough = Some(value);
+        self
+    }
+
+    /// Get the font style flags, where <(NAME, VALUE)>
+    fn flags(&self) -> [(&'static str, Option<bool>); 4] {
+        [("bold", self.bold), ("italic", self.italic), ("underline", self.underline), ("strikethrough", self.strikethrough)]
+    }
+}
+
+impl Serialize for SemanticTokenStyle {
+    /// Writes the style as color string (if only the foreground is set) or style object
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: serde::Serializer
+    {
+        use serde::ser::SerializeMap;
+
+        let flags = self.flags();
+        if let (Some(color), true) = (&self.foreground, flags.iter().all(|(_, v)| v.is_none())) {
+            return serializer.serialize_str(color);
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(color) = &self.foreground {
+            map.serialize_entry("foreground", color)?;
+        }
+        for (name, value) in flags {
+            if let Some(value) = value {
+                map.serialize_entry(name, &value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SemanticTokenStyle {
+    /// Reads the style from color string or style object
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Color(String),
+            Style {
+                foreground: Option<String>,
+                bold: Option<bool>,
+                italic: Option<bool>,
+                underline: Option<bool>,
+                strikethrough: Option<bool>,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Color(color) => Self::color(color),
+            Repr::Style { foreground, bold, italic, underline, strikethrough } => Self { foreground, bold, italic, underline, strikethrough },
+        })
+    }
+}
+
+
+/// Writes the scope selectors as string (if single) or array
+fn ser_scope<S>(scope: &[String], serializer: S) -> Result<S::Ok, S::Error>
+where S: serde::Serializer
+{
+    match scope {
+        [single] => serializer.serialize_str(single),
+        _ => scope.serialize(serializer),
+    }
+}
+
+/// Reads the scope selectors from comma separated string or array
+fn de_scope<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where D: serde::Deserializer<'de>
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Repr::deserialize(deserializer)? {
+        Repr::One(scope) => crate::tools::split_languages(&scope),
+        Repr::Many(scopes) => scopes,
+    })
+}
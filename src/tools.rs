@@ -29,3 +29,171 @@ pub fn to_latin_text(text: &str, rm_spaces: bool) -> String {
 
     text.to_string()
 }
+
+/// Splits the comma separated language ids list (example: "javascript,typescript")
+/// * langs - the language ids list
+pub fn split_languages(langs: &str) -> Vec<String> {
+    let mut list: Vec<String> = vec![];
+    for lang in langs.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if !list.iter().any(|l| l == lang) {
+            list.push(lang.to_owned());
+        }
+    }
+    list
+}
+
+/// Removes the comments && trailing commas from JSONC text (the JSON with comments)
+/// * text - the input JSONC text
+pub fn strip_json_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => if let Some(next) = chars.next() { out.push(next) },
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            // line comment:
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' { out.push('\n'); break; }
+                }
+            }
+            // block comment:
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' { break; }
+                    if c == '\n' { out.push('\n'); }
+                    prev = c;
+                }
+            }
+            // trailing comma:
+            (']' | '}', _) => {
+                let trimmed = out.trim_end().len();
+                if out[..trimmed].ends_with(',') {
+                    out.replace_range(trimmed - 1..trimmed, "");
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Escapes the markdown table cell text (the '|' would split the cell)
+/// * cell - the cell text
+pub fn escape_table_cell(cell: &str) -> String {
+    cell.replace('|', "\\|")
+}
+
+/// Generates the markdown table with aligned columns (the cells are escaped)
+/// * headers - the column headers (example: ["Key:", "Command:"])
+/// * rows - the table rows (the cells count is the same as headers count)
+pub fn markdown_table<S>(headers: &[&str], rows: &[Vec<S>]) -> String
+where S: AsRef<str>
+{
+    let rows = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| escape_table_cell(cell.as_ref())).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // calculating the columns width:
+    let mut widths = headers.iter().map(|h| h.chars().count()).collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let cells = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width.saturating_sub(cell.chars().count()))))
+            .collect::<Vec<_>>();
+        format!("| {} |\n", cells.join(" | "))
+    };
+
+    let dashes = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+    let mut table = line(headers.to_vec());
+    table.push_str(&line(dashes.iter().map(String::as_str).collect()));
+    for row in &rows {
+        table.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    table
+}
+
+/// The JSON object which keeps the keys order, where <(KEY, VALUE)>
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OrderedMap<K, V>(pub Vec<(K, V)>);
+
+impl<K, V> OrderedMap<K, V> {
+    /// Check the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<K, V> std::default::Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<K, V> serde::Serialize for OrderedMap<K, V>
+where K: serde::Serialize, V: serde::Serialize
+{
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where S: serde::Serializer
+    {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+impl<'de, K, V> serde::Deserialize<'de> for OrderedMap<K, V>
+where K: serde::Deserialize<'de>, V: serde::Deserialize<'de>
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where D: serde::Deserializer<'de>
+    {
+        struct Visitor<K, V>(std::marker::PhantomData<(K, V)>);
+
+        impl<'de, K, V> serde::de::Visitor<'de> for Visitor<K, V>
+        where K: serde::Deserialize<'de>, V: serde::Deserialize<'de>
+        {
+            type Value = OrderedMap<K, V>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a JSON object")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            where A: serde::de::MapAccess<'de>
+            {
+                let mut entries = vec![];
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(OrderedMap(entries))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor(std::marker::PhantomData))
+    }
+}
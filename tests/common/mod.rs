@@ -0,0 +1,40 @@
+#![allow(dead_code)]
+use vscode_generator::{ Package, Snippets, Snippet, License };
+use std::path::PathBuf;
+
+/// Creates the empty temporary directory for test output
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Creates the package without contributions
+/// * id - the id name of the package
+/// * name - the package name
+/// * description - the package description
+pub fn empty_package(id: &str, name: &str, description: &str) -> Package {
+    Package::new(id, name, description, "1.0.0".parse().unwrap(), "images/icon.png", None::<&str>, License::mit("DrakeN-inc"))
+}
+
+/// Creates the Rust snippets package with 'Blocks' && 'Operators' groups
+pub fn snippets_package() -> Package {
+    Package::snippets(
+        "vscode_rust_snippets",
+        "VSCode Rust snippets",
+        "The snippets for Rust programming language",
+        "0.0.1".parse().unwrap(),
+        "images/icon.png",
+        Some("https://github.com/DrakeN-inc/vscode-rust-snippets"),
+        vec![
+            Snippets::new("rust", "Blocks", "The block snippets", vec![
+                Snippet::block("block-struct", "struct"),
+                Snippet::simple_block("block-loop", "loop"),
+            ]),
+            Snippets::new("rust", "Operators", "The operator snippets", vec![
+                Snippet::operator("operator-return", "return", Some("")),
+            ]),
+        ],
+        License::mit("DrakeN-inc"),
+    )
+}
@@ -0,0 +1,153 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Package, Configuration, Setting, SettingType, SettingScope, SettingsModel, SettingsDoc };
+use serde::Serialize;
+use serde_json::json;
+use std::path::PathBuf;
+
+mod common;
+use common::{ temp_dir, empty_package };
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Settings {
+    enable: bool,
+    max_items: u32,
+    ratio: f64,
+    mode: String,
+    exclude: Vec<String>,
+    legacy_path: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { enable: true, max_items: 100, ratio: 0.5, mode: "auto".into(), exclude: vec!["target".into()], legacy_path: None }
+    }
+}
+
+impl SettingsModel for Settings {
+    const PREFIX: &'static str = "teamTools";
+
+    fn describe(config: Configuration) -> Configuration {
+        config
+            .update("enable", |s| s.set_descr("Enables the team tools"))
+            .update("teamTools.maxItems", |s| s.set_markdown_descr("The **maximal** items count").set_scope(SettingScope::Resource))
+            .update("mode", |s| s.add_enum("auto", "Detect automatically").add_enum("manual", "Ask every time").set_order(1))
+            .update("legacyPath", |s| s.set_type(SettingType::String).add_type(SettingType::Null).set_deprecated("Use `exclude` instead"))
+    }
+}
+
+#[test]
+fn derive_configuration() -> Result<()> {
+    let config = Configuration::derive::<Settings>("Team tools")?;
+    assert!(config.validate().is_ok());
+
+    // the settings keep the struct fields order:
+    let keys = config.settings().iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>();
+    assert_eq!(keys, ["teamTools.enable", "teamTools.maxItems", "teamTools.ratio", "teamTools.mode", "teamTools.exclude", "teamTools.legacyPath"]);
+
+    let types = config.settings().iter().map(|(_, s)| s.types_str()).collect::<Vec<_>>();
+    assert_eq!(types, ["boolean", "integer", "number", "string", "array", "string or null"]);
+
+    let json: serde_json::Value = serde_json::from_str(&config.to_json()?)?;
+    assert_eq!(json["properties"]["teamTools.maxItems"], json!({
+        "type": "integer", "default": 100, "markdownDescription": "The **maximal** items count", "scope": "resource"
+    }));
+    assert_eq!(json["properties"]["teamTools.mode"], json!({
+        "type": "string", "default": "auto", "enum": ["auto", "manual"], "enumDescriptions": ["Detect automatically", "Ask every time"], "order": 1
+    }));
+    assert_eq!(json["properties"]["teamTools.legacyPath"], json!({ "type": ["string", "null"], "deprecationMessage": "Use `exclude` instead" }));
+
+    // the settings table:
+    let doc = SettingsDoc::from_configuration(&config).to_string();
+    assert!(doc.starts_with("# Team tools [settings]:\n| Setting:"));
+    assert!(doc.contains("| `teamTools.maxItems`   | integer        | `100`        | The **maximal** items count"));
+    assert!(doc.contains("(one of: `\"auto\"`, `\"manual\"`)"));
+    assert!(doc.contains("| `teamTools.legacyPath` | string or null |              | **Deprecated:** Use `exclude` instead"));
+
+    // the '|' doesn't split the table cells:
+    let config = Configuration::new("Pipes").add_setting("x.sep", Setting::new(SettingType::String).set_default("|").set_descr("The a | b separator"));
+    assert!(SettingsDoc::from_configuration(&config).to_string().contains("| `x.sep`  | string | `\"\\|\"`   | The a \\| b separator |"));
+
+    Ok(())
+}
+
+#[test]
+fn validate_configuration() {
+    let err = Configuration::new("Test")
+        .add_setting("test.mode", Setting::with_default("fast").add_enum("slow", "").add_enum("auto", ""))
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Invalid setting 'test.mode': the default value \"fast\" isn't in enum");
+
+    assert!(Configuration::new("Test").add_setting("test.count", Setting::new(SettingType::Integer).set_default(1.5)).validate().is_err());
+    assert!(Configuration::new("Test").add_setting("test.ratio", Setting::new(SettingType::Number).set_default(1)).validate().is_ok());
+    assert!(Configuration::new("Test").add_setting("test.flag", Setting::new(SettingType::Boolean).add_enum("yes", "")).validate().is_err());
+    assert!(Configuration::new("Test").add_setting("test key", Setting::with_default(true)).validate().is_err());
+    assert!(Configuration::from_defaults("Test", "test", &42).is_err());
+
+    // the 'None' default requires the explicit type:
+    let err = Configuration::from_defaults("Test", "test", &json!({ "path": null })).unwrap().validate().unwrap_err();
+    assert_eq!(err.to_string(), "Invalid setting 'test.path': the type is 'null' (the type of 'None' default must be set explicitly)");
+    assert!(Configuration::new("Test").add_setting("test.path", Setting::new(SettingType::String).add_type(SettingType::Null).set_default(json!(null))).validate().is_ok());
+
+    // the type list && the missing type:
+    let config: Configuration = serde_json::from_value(json!({ "title": "Test", "properties": {
+        "test.path": { "type": ["string", "null"], "default": "~" },
+        "test.any": { "default": 1 },
+    }})).unwrap();
+    assert!(config.validate().is_ok());
+    assert_eq!(config.get("test.path").unwrap().types, [SettingType::String, SettingType::Null]);
+    assert!(config.get("test.any").unwrap().types.is_empty());
+    assert!(!serde_json::to_value(config.get("test.any").unwrap()).unwrap().as_object().unwrap().contains_key("type"));
+    assert!(Setting::new(SettingType::String).add_type(SettingType::Null).set_default(1).check().is_err());
+}
+
+#[test]
+fn configuration_package() -> Result<()> {
+    let mut pkg = empty_package("team-tools", "Team tools", "The team tools");
+    pkg.add_configuration(Configuration::derive::<Settings>("Team tools")?);
+
+    let json: serde_json::Value = serde_json::from_str(&pkg.to_json()?)?;
+    assert_eq!(json["contributes"]["configuration"][0]["title"], "Team tools");
+    assert_eq!(json["contributes"]["configuration"][0]["properties"]["teamTools.enable"], json!({
+        "type": "boolean", "default": true, "description": "Enables the team tools"
+    }));
+
+    let files = pkg.render()?;
+    let readme = String::from_utf8_lossy(&files[&PathBuf::from("README.md")]).to_string();
+    assert!(readme.contains("# Team tools [settings]:"));
+
+    // the invalid setting isn't written:
+    let mut broken = pkg.clone();
+    broken.add_configuration(Configuration::new("Broken").add_setting("x.y", Setting::new(SettingType::Boolean).set_default("no")));
+    assert!(matches!(broken.render(), Err(Error::InvalidSetting { .. })));
+
+    // loading the configuration (the single section object is accepted too):
+    let dir = temp_dir("configuration_package");
+    pkg.write_to(&dir)?;
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.contributes().configuration, pkg.contributes().configuration);
+
+    let mut manifest: serde_json::Value = serde_json::from_str(&pkg.to_json()?)?;
+    manifest["contributes"]["configuration"] = manifest["contributes"]["configuration"][0].clone();
+    std::fs::write(dir.join("package.json"), manifest.to_string())?;
+    assert_eq!(Package::load(&dir)?.0.contributes().configuration.len(), 1);
+
+    // the untitled section && the unknown schema fields:
+    manifest["contributes"]["configuration"] = json!({ "properties": {
+        "teamTools.paths": { "type": "array", "items": { "type": "string" }, "markdownEnumDescriptions": ["a"] },
+        "teamTools.depth": { "type": "integer", "minimum": 1, "default": 2 },
+    }});
+    std::fs::write(dir.join("package.json"), manifest.to_string())?;
+    let loaded = Package::load(&dir)?.0;
+    let config = &loaded.contributes().configuration[0];
+    assert_eq!(config.title, "");
+    assert_eq!(config.get("teamTools.depth").unwrap().extra["minimum"], 1);
+
+    let json: serde_json::Value = serde_json::from_str(&loaded.to_json()?)?;
+    assert_eq!(json["contributes"]["configuration"][0], manifest["contributes"]["configuration"]);
+    assert!(SettingsDoc::from_configuration(config).to_string().starts_with("# Settings [settings]:"));
+    assert_eq!(serde_json::to_value(Setting::new(SettingType::Integer).set_extra("minimum", 0))?, json!({ "type": "integer", "minimum": 0 }));
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Snippet, SnippetBody, SnippetNode, SnippetTransform, CaseModifier, SnippetVariable, VariableResolver, ExpansionContext };
+
+#[test]
+fn expand_double_block() -> Result<()> {
+    let exp = Snippet::double_block("block-struct-impl", "struct", "impl").expand(&ExpansionContext::new())?;
+
+    assert_eq!(exp.text, "struct  {\n    \n}\n\nimpl  {\n    \n}");
+    assert_eq!(exp.tabstops.iter().map(|t| t.index).collect::<Vec<_>>(), [1, 2, 3, 0]);
+
+    // the tabstop '$1' is mirrored to the both blocks:
+    let first = exp.tabstop(1).unwrap();
+    assert_eq!(first.ranges.iter().map(|r| exp.position(r.start)).collect::<Vec<_>>(), [(0, 7), (4, 5)]);
+    assert_eq!(exp.position(exp.tabstop(2).unwrap().ranges[0].start), (1, 4));
+    assert_eq!(exp.position(exp.tabstop(3).unwrap().ranges[0].start), (5, 4));
+
+    // the final cursor is at the end:
+    let end = exp.text.chars().count();
+    assert_eq!(exp.tabstop(0).unwrap().ranges[0], end..end);
+
+    Ok(())
+}
+
+#[test]
+fn expand_placeholders_and_variables() -> Result<()> {
+    let ctx = ExpansionContext::new()
+        .set_file("/src/models/user.rs")
+        .set_selection("value")
+        .set_language("python")
+        .set_date(chrono::NaiveDate::from_ymd_opt(2024, 7, 9).unwrap().and_hms_opt(8, 5, 0).unwrap());
+
+    let snippet = Snippet::new("test", "test", "test", vec![
+        "$LINE_COMMENT $TM_FILENAME_BASE ($CURRENT_YEAR-$CURRENT_MONTH-$CURRENT_DATE)",
+        "let ${1:name}: ${2|u8,u16|} = ${TM_SELECTED_TEXT:0}; // $1 ${CLIPBOARD:empty}$0",
+    ]);
+    let exp = snippet.expand(&ctx)?;
+
+    assert_eq!(exp.text, "# user (2024-07-09)\nlet name: u8 = value; // name empty");
+    let first = exp.tabstop(1).unwrap();
+    assert_eq!(first.ranges.iter().map(|r| exp.slice(r)).collect::<Vec<_>>(), ["name", "name"]);
+    assert_eq!(exp.slice(&exp.tabstop(2).unwrap().ranges[0]), "u8");
+    assert_eq!(exp.position(exp.tabstop(0).unwrap().ranges[0].start), (1, 35));
+
+    // the repeated placeholder mirrors the first one:
+    let exp = Snippet::new("test", "test", "test", vec!["${1:foo} ${1:bar} ${2:$1-${3:x}} ${2:y}"]).expand(&ctx)?;
+    assert_eq!(exp.text, "foo foo foo-x foo-x");
+    assert_eq!(exp.tabstop(1).unwrap().ranges.iter().map(|r| exp.slice(r)).collect::<Vec<_>>(), ["foo", "foo", "foo"]);
+    assert_eq!(exp.tabstop(3).unwrap().ranges.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn resolve_variables() -> Result<()> {
+    let ctx = ExpansionContext::new()
+        .set_workspace("/home/dev/project")
+        .set_file("/home/dev/project/src/main.rs")
+        .set_timezone(-330)
+        .set_seed(42);
+
+    assert_eq!(ctx.resolve(SnippetVariable::RelativeFilepath).as_deref(), Some("src/main.rs"));
+    assert_eq!(ctx.resolve(SnippetVariable::TmDirectoryBase).as_deref(), Some("src"));
+    assert_eq!(ctx.resolve(SnippetVariable::WorkspaceName).as_deref(), Some("project"));
+    assert_eq!(ctx.resolve(SnippetVariable::CurrentTimezoneOffset).as_deref(), Some("-05:30"));
+
+    // the random values are deterministic:
+    let uuid = ctx.resolve(SnippetVariable::Uuid).unwrap();
+    assert_eq!(uuid.len(), 36);
+    assert_eq!(&uuid[14..15], "4");
+    assert_eq!(ctx.clone().resolve(SnippetVariable::Uuid), Some(uuid));
+    assert_eq!(ctx.resolve(SnippetVariable::RandomHex).unwrap().len(), 6);
+    assert_ne!(ctx.resolve(SnippetVariable::Random), ctx.clone().set_seed(7).resolve(SnippetVariable::Random));
+
+    // the custom resolver:
+    struct Fixed;
+    impl VariableResolver for Fixed {
+        fn resolve(&self, var: SnippetVariable) -> Option<String> {
+            Some(var.as_str().to_lowercase())
+        }
+    }
+
+    let exp = Snippet::new("test", "test", "test", vec!["$CURRENT_YEAR ${UUID:x}$0"]).expand(&Fixed)?;
+    assert_eq!(exp.text, "current_year uuid");
+    assert_eq!("CURSOR_NUMBER".parse(), Ok(SnippetVariable::CursorNumber));
+    assert_eq!(SnippetVariable::ALL.len(), 35);
+
+    Ok(())
+}
+
+#[test]
+fn regex_transforms() -> Result<()> {
+    let class_name = SnippetTransform::new("(.*)\\..+$").case(1, CaseModifier::Pascalcase);
+    let getter = SnippetTransform::new("^(\\w)(.*)$").text("get").case(1, CaseModifier::Upcase).group(2);
+    let plural = SnippetTransform::new("(y)?$").conditional(1, Some("ies"), Some("s")).options("i");
+    let dashes = SnippetTransform::new("_").text("-").options("g");
+
+    assert_eq!(class_name.to_string(), "/(.*)\\..+$/${1:/pascalcase}/");
+    assert_eq!(plural.to_string(), "/(y)?$/${1:?ies:s}/i");
+    assert_eq!(class_name.apply("user_profile.rs").unwrap(), "UserProfile");
+    assert_eq!(CaseModifier::Camelcase.apply("user_profile"), "userProfile");
+    assert_eq!(dashes.apply("a_b_c").unwrap(), "a-b-c");
+
+    let body = SnippetBody::new()
+        .text("class ").variable_transform(SnippetVariable::TmFilename, &class_name)
+        .text(" { ").placeholder(1, "name").text(": ").tabstop_transform(1, &getter)
+        .text(", ").tabstop_transform(1, &plural).text(", ").variable_transform("TM_FILENAME_BASE", &dashes)
+        .text(" }");
+    let snippet = Snippet::new("test", "test", "test", body);
+
+    // the builder output is parsed back to the same transforms:
+    let nodes = snippet.syntax_tree()?;
+    assert_eq!(nodes[1], SnippetNode::Variable { name: SnippetVariable::TmFilename, default: None, transform: Some(class_name) });
+    assert_eq!(nodes[5], SnippetNode::Tabstop { index: 1, transform: Some(getter) });
+
+    let exp = snippet.expand(&ExpansionContext::new().set_file("/src/user_profile.rs"))?;
+    assert_eq!(exp.text, "class UserProfile { name: getName, names, user-profile }");
+
+    let exp = Snippet::new("test", "test", "test", vec!["${1:city} ${1/(y)?$/${1:?ies:s}/}"]).expand(&ExpansionContext::new())?;
+    assert_eq!(exp.text, "city cities");
+    assert_eq!(exp.tabstop(1).unwrap().ranges.iter().map(|r| exp.slice(r)).collect::<Vec<_>>(), ["city"]);
+
+    // the JavaScript look-behinds && back-references are supported:
+    let exp = Snippet::new("test", "test", "test", vec!["${TM_FILENAME/(?<=a)b/x/} ${TM_FILENAME_BASE/(.)\\1/$1/g}"])
+        .expand(&ExpansionContext::new().set_file("/src/aabb.rs"))?;
+    assert_eq!(exp.text, "aaxb.rs ab");
+    assert_eq!(SnippetTransform::new("(?<=_)(\\w)").case(1, CaseModifier::Upcase).options("g").apply("a_b_c").unwrap(), "a_B_C");
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Package, Grammar, Rule, Capture, Category };
+use std::path::PathBuf;
+
+mod common;
+use common::{ temp_dir, empty_package };
+
+fn sample_grammar() -> Grammar {
+    Grammar::new("mydsl", "MyDSL", "source.mydsl")
+        .add_pattern(Rule::include("#keywords"))
+        .add_pattern(Rule::include("#strings"))
+        .add_pattern(Rule::include("#heredoc"))
+        .add_rule("keywords", Rule::matches(r"\b(let|fn|if|else)\b(?=\s)", "keyword.control.mydsl"))
+        .add_rule("strings", Rule::block("\"", "\"", "string.quoted.double.mydsl")
+            .add_pattern(Rule::matches(r"\\.", "constant.character.escape.mydsl"))
+        )
+        .add_rule("heredoc", Rule::block(r"<<(\w+)", r"^\1$", "string.unquoted.heredoc.mydsl")
+            .begin_capture(1, "entity.name.tag.mydsl")
+            .set_content_name("meta.embedded.block.sql")
+            .add_pattern(Rule::include("source.sql"))
+        )
+        .add_rule("quote", Rule::while_block("^>", "^>", "markup.quote.mydsl")
+            .capture(0, Capture::new("punctuation.mydsl").add_pattern(Rule::include("$self")))
+        )
+        .embed_language("meta.embedded.block.sql", "sql")
+}
+
+#[test]
+fn grammar_json() -> Result<()> {
+    let grammar = sample_grammar();
+    assert_eq!(grammar.file_name, PathBuf::from("mydsl.tmLanguage.json"));
+    assert!(grammar.validate().is_ok());
+
+    let json: serde_json::Value = serde_json::from_str(&grammar.to_json()?)?;
+    assert_eq!(json["scopeName"], "source.mydsl");
+    assert_eq!(json["patterns"][0], serde_json::json!({ "include": "#keywords" }));
+    assert_eq!(json["repository"]["heredoc"]["beginCaptures"], serde_json::json!({ "1": { "name": "entity.name.tag.mydsl" } }));
+    assert_eq!(json["repository"]["quote"]["while"], "^>");
+    assert_eq!(json["repository"]["keywords"]["match"], r"\b(let|fn|if|else)\b(?=\s)");
+    assert!(json.get("injectionSelector").is_none());
+
+    // the round trip:
+    let loaded = Grammar::from_json(Some("mydsl"), &grammar.to_json()?)?;
+    assert_eq!(loaded.repository(), grammar.repository());
+    assert_eq!(loaded.language.as_deref(), Some("mydsl"));
+
+    Ok(())
+}
+
+#[test]
+fn nested_repository() -> Result<()> {
+    let json = r##"{
+        "name": "MyDSL",
+        "scopeName": "source.mydsl",
+        "fileTypes": ["mydsl"],
+        "firstLineMatch": "^#!.*\\bmydsl\\b",
+        "uuid": "0d4a7c6e",
+        "patterns": [{ "include": "#block" }],
+        "repository": {
+            "block": {
+                "comment": "the braced block with own rules",
+                "begin": "\\{",
+                "end": "\\}",
+                "applyEndPatternLast": 1,
+                "patterns": [{ "include": "#inner" }, { "include": "#block" }],
+                "repository": {
+                    "inner": { "match": "\\w+", "name": "variable.mydsl", "disabled": 0 }
+                }
+            }
+        }
+    }"##;
+
+    // the nested repository && the other fields are kept:
+    let grammar = Grammar::from_json(Some("mydsl"), json)?;
+    assert!(grammar.validate().is_ok());
+    assert_eq!(grammar.file_types, ["mydsl"]);
+    assert_eq!(grammar.first_line_match.as_deref(), Some(r"^#!.*\bmydsl\b"));
+    assert_eq!(grammar.extra["uuid"], "0d4a7c6e");
+
+    let block = grammar.rule("block").unwrap();
+    assert_eq!(block.apply_end_pattern_last, Some(true));
+    assert_eq!(block.comment.as_deref(), Some("the braced block with own rules"));
+    assert_eq!(block.rule("inner").unwrap().extra["disabled"], 0);
+
+    let written: serde_json::Value = serde_json::from_str(&grammar.to_json()?)?;
+    assert_eq!(written["repository"]["block"]["repository"]["inner"]["disabled"], 0);
+    assert_eq!(written["fileTypes"], serde_json::json!(["mydsl"]));
+    assert_eq!(Grammar::from_json(Some("mydsl"), &grammar.to_json()?)?, grammar);
+
+    // the nested repository isn't visible outside of its rule:
+    let err = grammar.clone().add_pattern(Rule::include("#inner")).validate().unwrap_err();
+    assert!(err.to_string().ends_with("patterns[1]: the include '#inner' isn't found in repository"));
+
+    let grammar = sample_grammar()
+        .set_first_line_match("^#!")
+        .add_rule("list", Rule::block(r"\[", r"\]", "meta.list.mydsl")
+            .set_apply_end_pattern_last(true)
+            .add_pattern(Rule::include("#item"))
+            .add_rule("item", Rule::matches(r"\d+", "constant.numeric.mydsl").add_pattern(Rule::include("#keywords")))
+        );
+    assert!(grammar.validate().is_ok());
+    assert!(sample_grammar().set_first_line_match("^#!(").validate().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn validate_grammar() {
+    let err = sample_grammar().add_pattern(Rule::include("#comments")).validate().unwrap_err();
+    assert_eq!(err.to_string(), "Invalid grammar 'source.mydsl': patterns[3]: the include '#comments' isn't found in repository");
+
+    let err = sample_grammar()
+        .add_rule("numbers", Rule::group(vec![Rule::matches(r"\d+(", "constant.numeric.mydsl")]))
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains(r"repository.numbers.patterns[0]: the match regex '\d+(' is invalid"));
+
+    // the rules structure:
+    let mut rule = Rule::block("a", "b", "c");
+    rule.while_ = Some("d".into());
+    assert!(sample_grammar().add_rule("both", rule).validate().is_err());
+    assert!(sample_grammar().add_rule("include", Rule::include("#strings").set_name("x")).validate().is_ok());
+    let mut rule = Rule::default();
+    rule.end = Some("x".into());
+    assert!(sample_grammar().add_rule("end", rule).validate().is_err());
+
+    // the Oniguruma-only escapes:
+    assert!(sample_grammar().add_rule("hex", Rule::matches(r"\b0x\h+\b|[\h_]+\H", "constant.numeric.mydsl")).validate().is_ok());
+    assert!(sample_grammar().add_rule("eof", Rule::block(r"^__END__$", r"\Z", "comment.block.mydsl")).validate().is_ok());
+
+    // the back-references are allowed in 'end' only:
+    assert!(sample_grammar().add_rule("backref", Rule::matches(r"^\1$", "x")).validate().is_err());
+    assert!(Grammar::new("mydsl", "MyDSL", "source mydsl").validate().is_err());
+}
+
+#[test]
+fn grammar_package() -> Result<()> {
+    let injection = Grammar::injection("MyDSL todo", "todo.injection", "L:comment")
+        .add_pattern(Rule::matches(r"\bTODO\b", "keyword.todo"))
+        .inject_to("source.mydsl");
+
+    let mut pkg = empty_package("mydsl", "MyDSL", "The MyDSL language support");
+    pkg.add_grammar(sample_grammar());
+    pkg.add_grammar(injection);
+    assert_eq!(pkg.categories(), [Category::ProgrammingLanguages]);
+
+    let json: serde_json::Value = serde_json::from_str(&pkg.to_json()?)?;
+    assert_eq!(json["contributes"]["grammars"], serde_json::json!([
+        { "language": "mydsl", "scopeName": "source.mydsl", "path": "syntaxes/mydsl.tmLanguage.json", "embeddedLanguages": { "meta.embedded.block.sql": "sql" } },
+        { "scopeName": "todo.injection", "path": "syntaxes/mydsl-todo.tmLanguage.json", "injectTo": ["source.mydsl"] },
+    ]));
+
+    let files = pkg.render()?;
+    assert!(files.contains_key(&PathBuf::from("syntaxes/mydsl.tmLanguage.json")));
+    assert!(String::from_utf8_lossy(&files[&PathBuf::from("syntaxes/mydsl-todo.tmLanguage.json")]).contains("\"injectionSelector\": \"L:comment\""));
+
+    // the invalid grammar isn't written:
+    let mut broken = pkg.clone();
+    broken.add_grammar(Grammar::new("x", "X", "source.x").add_pattern(Rule::include("#missing")));
+    assert!(matches!(broken.render(), Err(Error::InvalidGrammar { .. })));
+
+    // loading the grammars:
+    let dir = temp_dir("grammar_package");
+    pkg.write_to(&dir)?;
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.grammars(), pkg.grammars());
+
+    Ok(())
+}
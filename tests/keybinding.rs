@@ -0,0 +1,111 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Package, Category, Keybinding, KeyChord, Platform, DiagnosticKind };
+use std::path::PathBuf;
+
+mod common;
+use common::{ temp_dir, empty_package };
+
+fn sample_package() -> Package {
+    let mut pkg = empty_package("team-keymap", "Team keymap", "The team keybindings");
+    pkg.add_keybinding(Keybinding::new("ctrl+k ctrl+c", "editor.action.addCommentLine")
+        .set_mac("cmd+k cmd+c")
+        .set_when("editorTextFocus && !editorReadonly")
+        .set_descr("Comment the lines")
+    );
+    pkg.add_keybinding(Keybinding::new("ctrl+alt+t", "editor.action.insertSnippet")
+        .set_args(serde_json::json!({ "name": "todo" }))
+        .set_descr("Insert TODO")
+    );
+    pkg
+}
+
+#[test]
+fn key_chords() {
+    let chord: KeyChord = "Shift+Ctrl+K".parse().unwrap();
+    assert_eq!(chord.to_string(), "ctrl+shift+k");
+    assert_eq!("cmd+[KeyA]".parse::<KeyChord>().unwrap().to_string(), "meta+[KeyA]");
+    assert!("ctrl+f12".parse::<KeyChord>().is_ok() && "numpad_add".parse::<KeyChord>().is_ok());
+
+    assert_eq!("ctrl+ctrl+k".parse::<KeyChord>().unwrap_err(), "the chord 'ctrl+ctrl+k' has duplicate modifier 'ctrl'");
+    assert_eq!("hyper+k".parse::<KeyChord>().unwrap_err(), "the modifier 'hyper' is unknown");
+    assert!("ctrl+".parse::<KeyChord>().is_err() && "ctrl+f20".parse::<KeyChord>().is_err() && "ctrl+enterr".parse::<KeyChord>().is_err());
+
+    let kb = Keybinding::new("ctrl+k ctrl+c", "cmd").set_mac("cmd+k cmd+c");
+    assert_eq!(kb.key_for(Platform::Mac), "cmd+k cmd+c");
+    assert_eq!(kb.chords(Platform::Linux).unwrap().len(), 2);
+    assert!(kb.validate().is_ok());
+
+    let err = kb.set_win("ctrl+k ctrl+x+").validate().unwrap_err();
+    assert_eq!(err.to_string(), "Invalid keybinding 'ctrl+k ctrl+c': win: the modifier 'x' is unknown");
+    assert!(Keybinding::new("ctrl+k", " ").validate().is_err());
+}
+
+#[test]
+fn keybinding_conflicts() {
+    let a = Keybinding::new("ctrl+k", "a").set_when("editorTextFocus && editorLangId == rust");
+    let b = Keybinding::new("Ctrl+K", "b").set_when("editorLangId == 'rust' || terminalFocus");
+    let c = Keybinding::new("ctrl+k", "c").set_when("editorLangId == python");
+    let d = Keybinding::new("ctrl+k", "d").set_when("!editorTextFocus");
+    let e = Keybinding::new("ctrl+k", "e").set_mac("cmd+k");
+
+    let conflicts = Keybinding::conflicts(&[a.clone(), b.clone()]);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].platforms, Platform::ALL);
+    assert_eq!(conflicts[0].to_string(), "'ctrl+k' [mac, linux, win]: a, b");
+
+    // the contradicting clauses:
+    assert!(Keybinding::conflicts(&[a.clone(), c.clone()]).is_empty());
+    assert!(Keybinding::conflicts(&[a.clone(), d]).is_empty());
+
+    // the keybinding without clause overlaps with any clause (on the same platforms only):
+    let conflicts = Keybinding::conflicts(&[c, e]);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].platforms, [Platform::Linux, Platform::Windows]);
+}
+
+#[test]
+fn keybinding_package() -> Result<()> {
+    let pkg = sample_package();
+    assert_eq!(pkg.categories(), [Category::Keymaps]);
+
+    let json: serde_json::Value = serde_json::from_str(&pkg.to_json()?)?;
+    assert_eq!(json["contributes"]["keybindings"], serde_json::json!([
+        { "key": "ctrl+k ctrl+c", "mac": "cmd+k cmd+c", "command": "editor.action.addCommentLine", "when": "editorTextFocus && !editorReadonly" },
+        { "key": "ctrl+alt+t", "command": "editor.action.insertSnippet", "args": { "name": "todo" } },
+    ]));
+
+    let files = pkg.render()?;
+    let readme = String::from_utf8_lossy(&files[&PathBuf::from("README.md")]).to_string();
+    assert!(readme.ends_with(concat!(
+        "# Keybindings:\n",
+        "| Key:            | Mac:          | Command:                       | Description:      |\n",
+        "| --------------- | ------------- | ------------------------------ | ----------------- |\n",
+        "| `ctrl+k ctrl+c` | `cmd+k cmd+c` | `editor.action.addCommentLine` | Comment the lines |\n",
+        "| `ctrl+alt+t`    |               | `editor.action.insertSnippet`  | Insert TODO       |\n",
+    )));
+
+    // the invalid chord isn't written:
+    let mut broken = sample_package();
+    broken.add_keybinding(Keybinding::new("ctrl+shift", "x"));
+    assert!(matches!(broken.render(), Err(Error::InvalidKeybinding { .. })));
+
+    // the conflicts are reported by validation:
+    let mut pkg = sample_package();
+    pkg.add_keybinding(Keybinding::new("ctrl+alt+t", "workbench.action.terminal.new"));
+    let diags = pkg.validate(env!("CARGO_TARGET_TMPDIR"));
+    let conflict = diags.iter().find(|d| d.kind == DiagnosticKind::KeybindingConflict).unwrap();
+    assert!(!conflict.is_error());
+    assert_eq!(
+        conflict.to_string(),
+        "warning: the keybindings 'ctrl+alt+t' [mac, linux, win]: editor.action.insertSnippet, workbench.action.terminal.new have overlapping 'when' clauses"
+    );
+
+    // loading the keybindings:
+    let dir = temp_dir("keybinding_package");
+    sample_package().write_to(&dir)?;
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.contributes().keybindings.len(), 2);
+    assert_eq!(loaded.contributes().keybindings[1].args, Some(serde_json::json!({ "name": "todo" })));
+
+    Ok(())
+}
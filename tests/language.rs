@@ -0,0 +1,129 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Package, License, Snippets, Snippet, Category, LanguageDefinition, LanguageConfiguration, ConfigRegex, OnEnterRule, IndentAction };
+use std::path::PathBuf;
+
+mod common;
+use common::temp_dir;
+
+fn sample_language() -> LanguageDefinition {
+    LanguageDefinition::new("mydsl", "MyDSL")
+        .add_extension(".mydsl")
+        .add_filename("Mydslfile")
+        .set_configuration(LanguageConfiguration::new()
+            .set_line_comment("//")
+            .set_block_comment("/*", "*/")
+            .add_bracket("{", "}")
+            .add_bracket("(", ")")
+            .add_auto_closing_pair("{", "}", &[])
+            .add_auto_closing_pair("\"", "\"", &["string", "comment"])
+            .add_surrounding_pair("\"", "\"")
+            .set_folding_markers(r"^\s*//\s*#region\b", r"^\s*//\s*#endregion\b")
+            .set_word_pattern(r"[A-Za-z_][\w-]*")
+            .set_indentation_rules(r"\{[^}]*$", r"^\s*\}")
+            .add_on_enter_rule(OnEnterRule::new(r"^\s*/\*\*(?!/)([^\*]|\*(?!/))*$", IndentAction::IndentOutdent)
+                .after_text(r"^\s*\*/$")
+                .append_text(" * ")
+            )
+        )
+}
+
+#[test]
+fn language_configuration() -> Result<()> {
+    let lang = sample_language();
+    assert!(lang.validate().is_ok());
+    assert_eq!(lang.configuration_path(), Some(PathBuf::from("languages/mydsl.language-configuration.json")));
+
+    let json: serde_json::Value = serde_json::from_str(&lang.configuration.to_json()?)?;
+    assert_eq!(json["comments"], serde_json::json!({ "lineComment": "//", "blockComment": ["/*", "*/"] }));
+    assert_eq!(json["brackets"], serde_json::json!([["{", "}"], ["(", ")"]]));
+    assert_eq!(json["autoClosingPairs"], serde_json::json!([{ "open": "{", "close": "}" }, { "open": "\"", "close": "\"", "notIn": ["string", "comment"] }]));
+    assert_eq!(json["folding"]["markers"]["start"], r"^\s*//\s*#region\b");
+    assert_eq!(json["indentationRules"]["decreaseIndentPattern"], r"^\s*\}");
+    assert_eq!(json["onEnterRules"][0]["action"], serde_json::json!({ "indent": "indentOutdent", "appendText": " * " }));
+
+    // the round trip && the array pairs:
+    assert_eq!(LanguageConfiguration::from_json(&lang.configuration.to_json()?)?, lang.configuration);
+    let config = LanguageConfiguration::from_json(r#"{ "autoClosingPairs": [["[", "]"]], }"#)?;
+    assert_eq!(config.auto_closing_pairs[0].close, "]");
+
+    // the regex objects with flags:
+    let config = LanguageConfiguration::from_json(r#"{
+        "wordPattern": { "pattern": "[a-z]+", "flags": "iu" },
+        "folding": { "markers": { "start": "^#region", "end": { "pattern": "^#endregion" } } },
+        "indentationRules": { "increaseIndentPattern": { "pattern": "\\{$", "flags": "u" }, "decreaseIndentPattern": "^\\}" },
+        "onEnterRules": [{ "beforeText": { "pattern": "^// ", "flags": "i" }, "action": { "indent": "none", "appendText": "// " } }],
+    }"#)?;
+    assert_eq!(config.word_pattern, Some(ConfigRegex::new("[a-z]+").flags("iu")));
+    assert_eq!(config.folding.as_ref().and_then(|f| f.markers.as_ref()).map(|m| &m.end), Some(&ConfigRegex::new("^#endregion")));
+    assert_eq!(config.indentation_rules.as_ref().map(|r| r.increase_indent_pattern.pattern.as_str()), Some(r"\{$"));
+    assert_eq!(config.on_enter_rules[0].before_text.flags.as_deref(), Some("i"));
+    assert!(config.check().is_ok());
+
+    let json: serde_json::Value = serde_json::from_str(&config.to_json()?)?;
+    assert_eq!(json["wordPattern"], serde_json::json!({ "pattern": "[a-z]+", "flags": "iu" }));
+    assert_eq!(json["folding"]["markers"]["end"], "^#endregion");
+    assert_eq!(LanguageConfiguration::from_json(&config.to_json()?)?, config);
+
+    // the empty configuration isn't contributed:
+    assert_eq!(LanguageDefinition::new("plain", "Plain").configuration_path(), None);
+
+    Ok(())
+}
+
+#[test]
+fn validate_language() {
+    let err = sample_language()
+        .set_configuration(LanguageConfiguration::new().set_word_pattern("[a-z"))
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().starts_with("Invalid language 'mydsl': wordPattern: the regex '[a-z' is invalid"));
+
+    let config = LanguageConfiguration::new().add_on_enter_rule(OnEnterRule::new("^ok$", IndentAction::Indent).previous_line_text("(("));
+    assert!(config.check().unwrap_err().starts_with("onEnterRules[0].previousLineText"));
+
+    assert!(LanguageConfiguration::new().add_auto_closing_pair("<", ">", &["regex"]).check().is_err());
+    let config = LanguageConfiguration::new().set_word_pattern(ConfigRegex::new("[a-z]+").flags("ix"));
+    assert_eq!(config.check().unwrap_err(), "wordPattern: the regex '[a-z]+' has unknown flag 'x'");
+    assert!(sample_language().add_extension("mydsl").validate().is_err());
+    assert!(LanguageDefinition::new("my dsl", "MyDSL").validate().is_err());
+}
+
+#[test]
+fn language_package() -> Result<()> {
+    let mut pkg = Package::snippets(
+        "mydsl",
+        "MyDSL",
+        "The MyDSL language support",
+        "1.0.0".parse().unwrap(),
+        "images/icon.png",
+        None::<&str>,
+        vec![
+            Snippets::new("mydsl", "Keywords", "The keyword snippets", vec![
+                Snippet::text("let", "let", "let ${1:name} = $0"),
+            ]),
+        ],
+        License::mit("DrakeN-inc"),
+    );
+    pkg.add_language(sample_language());
+    pkg.add_language(LanguageDefinition::new("mydsl-log", "MyDSL log").add_extension(".mlog"));
+    assert_eq!(pkg.categories(), [Category::Snippets, Category::ProgrammingLanguages]);
+
+    let json: serde_json::Value = serde_json::from_str(&pkg.to_json()?)?;
+    assert_eq!(json["contributes"]["languages"], serde_json::json!([
+        { "id": "mydsl", "aliases": ["MyDSL"], "extensions": [".mydsl"], "filenames": ["Mydslfile"], "configuration": "languages/mydsl.language-configuration.json" },
+        { "id": "mydsl-log", "aliases": ["MyDSL log"], "extensions": [".mlog"] },
+    ]));
+    assert_eq!(json["contributes"]["snippets"][0]["language"], "mydsl");
+
+    let files = pkg.render()?;
+    assert!(files.contains_key(&PathBuf::from("languages/mydsl.language-configuration.json")));
+    assert_eq!(files.keys().filter(|p| p.starts_with("languages")).count(), 1);
+
+    // loading the languages:
+    let dir = temp_dir("language_package");
+    pkg.write_to(&dir)?;
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.languages(), pkg.languages());
+
+    Ok(())
+}
@@ -37,8 +37,8 @@ fn main() -> Result<()> {
                         "Display",
                         "Clone",
                         "Copy",
-                        "Eq\\, PartialEq",
-                        "Serialize\\, Deserialize",
+                        "Eq, PartialEq",
+                        "Serialize, Deserialize",
                     ])),
 
                     Snippet::attribute("attr-allow", "allow", Some(vec![
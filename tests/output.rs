@@ -0,0 +1,53 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, MemorySink, WriteAction };
+use std::path::PathBuf;
+
+mod common;
+use common::{ temp_dir, snippets_package };
+
+#[test]
+fn render_in_memory() -> Result<()> {
+    let files = snippets_package().render()?;
+    assert_eq!(files.keys().cloned().collect::<Vec<_>>(), [
+        PathBuf::from("LICENSE.md"), PathBuf::from("README.md"), PathBuf::from("package.json"),
+        PathBuf::from("snippets/blocks.code-snippets"), PathBuf::from("snippets/operators.code-snippets"),
+    ]);
+    assert!(String::from_utf8_lossy(&files[&PathBuf::from("LICENSE.md")]).starts_with("MIT License"));
+    assert_eq!(
+        String::from_utf8_lossy(&files[&PathBuf::from("snippets/operators.code-snippets")]),
+        "{\n  \"operator-return\": {\n    \"scope\": \"rust\",\n    \"description\": \"return ...;\",\n    \"prefix\": \"return \",\n    \"body\": [\n      \"return ${1:};\"\n    ]\n  }\n}"
+    );
+
+    // the single snippets group:
+    let mut sink = MemorySink::new();
+    let doc = snippets_package().snippets_groups()[1].write_into(&mut sink)?;
+    assert_eq!(sink.files.len(), 1);
+    assert!(sink.text("snippets/operators.code-snippets").unwrap().contains("\"prefix\": \"return \""));
+    assert!(doc.to_string().contains("return"));
+
+    Ok(())
+}
+
+#[test]
+fn dry_run() -> Result<()> {
+    let dir = temp_dir("dry_run");
+    let pkg = snippets_package();
+
+    let planned = pkg.dry_run(&dir)?;
+    assert!(planned.iter().all(|p| p.action == WriteAction::Create));
+    assert_eq!(planned[0].to_string(), format!("create    package.json ({} bytes)", pkg.to_json()?.len()));
+    assert!(!dir.exists());
+
+    pkg.write_to(&dir)?;
+    let planned = pkg.clone().set_descr("The updated description").dry_run(&dir)?;
+    let actions = planned.iter().map(|p| (p.path.to_string_lossy().to_string(), p.action)).collect::<Vec<_>>();
+    assert_eq!(actions, [
+        ("package.json".to_owned(), WriteAction::Overwrite),
+        ("snippets/blocks.code-snippets".to_owned(), WriteAction::Unchanged),
+        ("snippets/operators.code-snippets".to_owned(), WriteAction::Unchanged),
+        ("README.md".to_owned(), WriteAction::Overwrite),
+        ("LICENSE.md".to_owned(), WriteAction::Unchanged),
+    ]);
+
+    Ok(())
+}
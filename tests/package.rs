@@ -0,0 +1,295 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Package, Snippets, Snippet, License, LintKind, Category, Person, Bugs, GalleryBanner, BannerTheme, Pricing, Badge, Qna, DiagnosticKind, Severity };
+
+mod common;
+use common::{ temp_dir, snippets_package };
+
+#[test]
+fn load_package() -> Result<()> {
+    let dir = temp_dir("load_package");
+    let pkg = snippets_package();
+    pkg.write_to(&dir)?;
+
+    let (loaded, skipped) = Package::load(&dir)?;
+    assert!(skipped.is_empty());
+    assert_eq!(loaded.id(), "vscode_rust_snippets");
+    assert_eq!(loaded.name(), "VSCode Rust snippets");
+    assert_eq!(loaded.version().to_string(), "0.0.1");
+    assert_eq!(loaded.icon(), Some("images/icon.png"));
+    assert_eq!(loaded.repository().unwrap().url, "https://github.com/DrakeN-inc/vscode-rust-snippets");
+    assert_eq!(loaded.engines().vscode.to_string(), "^1.90.0");
+    assert_eq!(loaded.license().unwrap().as_str(), pkg.license().unwrap().as_str());
+
+    let groups = loaded.snippets_groups();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].languages, ["rust"]);
+    assert_eq!(groups[0].iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(), ["block-struct", "block-loop"]);
+    assert_eq!(groups[1].get("operator-return").unwrap().body, ["return ${1:};"]);
+
+    // the loaded package is written back without changes:
+    assert_eq!(loaded.to_json()?, pkg.to_json()?);
+    for (a, b) in loaded.snippets_groups().iter().zip(pkg.snippets_groups()) {
+        assert_eq!(a.to_json()?, b.to_json()?);
+    }
+
+    // the broken snippets are skipped && reported:
+    let path = dir.join("snippets").join(&pkg.snippets_groups()[1].file_name);
+    let json = std::fs::read_to_string(&path)?.replacen('{', r#"{ "broken": { "prefix": "br", "body": "${1:oops" },"#, 1);
+    std::fs::write(&path, json)?;
+
+    let (loaded, skipped) = Package::load(&dir)?;
+    assert!(loaded.snippets_groups()[1].get("operator-return").is_some());
+    assert_eq!(skipped.len(), 1);
+    assert_eq!((skipped[0].file.as_path(), skipped[0].key.as_str()), (path.strip_prefix(&dir).unwrap(), "broken"));
+
+    Ok(())
+}
+
+#[test]
+fn load_manifest_extra() -> Result<()> {
+    let dir = temp_dir("load_manifest_extra");
+    std::fs::create_dir_all(dir.join("snippets"))?;
+    std::fs::write(dir.join("snippets/all.code-snippets"), r#"{ "log": { "scope": "javascript", "prefix": "log", "body": "console.log($1);" } }"#)?;
+    std::fs::write(dir.join("package.json"), r#"{
+        "name": "extra",
+        "version": "1.0.0",
+        "engines": { "vscode": "^1.90.0" },
+        "main": "./out/extension.js",
+        "activationEvents": ["onLanguage:javascript"],
+        "scripts": { "compile": "tsc -p ./" },
+        "contributes": { "snippets": [{ "path": "./snippets/all.code-snippets" }] }
+    }"#)?;
+
+    // the snippets contribute without language is accepted && the unknown fields are kept:
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.contributes().snippets[0].language, None);
+    assert!(loaded.snippets_groups()[0].get("log").is_some());
+    assert_eq!(loaded.extra()["main"], "./out/extension.js");
+
+    let json: serde_json::Value = serde_json::from_str(&loaded.to_json()?)?;
+    assert_eq!(json["activationEvents"], serde_json::json!(["onLanguage:javascript"]));
+    assert_eq!(json["scripts"]["compile"], "tsc -p ./");
+    assert!(json["contributes"]["snippets"][0].get("language").is_none());
+
+    let json: serde_json::Value = serde_json::from_str(&snippets_package().set_extra("main", "./out/extension.js").to_json()?)?;
+    assert_eq!(json["main"], "./out/extension.js");
+
+    Ok(())
+}
+
+#[test]
+fn multi_language_contributes() -> Result<()> {
+    let pkg = Package::snippets(
+        "js_snippets",
+        "JS snippets",
+        "The snippets for JavaScript && TypeScript",
+        "1.0.0".parse()?,
+        "images/icon.png",
+        None::<&str>,
+        vec![
+            Snippets::new("javascript,typescript", "Logging", "The logging snippets", vec![
+                Snippet::text("log", "log", "console.log();"),
+                Snippet::text("log-jsx", "logx", "{console.log()}").set_lang("javascriptreact"),
+            ]),
+        ],
+        License::mit("DrakeN-inc"),
+    );
+
+    // the file with several scopes is contributed without language (so VS Code uses the snippets own scopes):
+    let contribs = pkg.contributes().snippets.iter()
+        .map(|c| (c.language.as_deref(), c.path.to_string_lossy().to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(contribs, [(None, "snippets/logging.code-snippets".to_owned())]);
+
+    let group = &pkg.snippets_groups()[0];
+    let json: serde_json::Value = serde_json::from_str(&group.to_json()?).unwrap();
+    assert_eq!(json["log"]["scope"], "javascript,typescript");
+    assert_eq!(json["log-jsx"]["scope"], "javascriptreact");
+    assert_eq!(group.snippet_languages(group.get("log").unwrap()), ["javascript", "typescript"]);
+    assert_eq!(group.snippet_languages(group.get("log-jsx").unwrap()), ["javascriptreact"]);
+
+    // the single language file is contributed for its language:
+    let rust = snippets_package();
+    assert_eq!(rust.contributes().snippets[0].language.as_deref(), Some("rust"));
+
+    // the loaded file keeps the snippets scopes:
+    let dir = temp_dir("multi_language_contributes");
+    pkg.write_to(&dir)?;
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.snippets_groups().len(), 1);
+    assert!(loaded.snippets_groups()[0].languages.is_empty());
+    assert_eq!(loaded.snippets_groups()[0].to_json()?, group.to_json()?);
+    assert_eq!(loaded.to_json()?, pkg.to_json()?);
+
+    Ok(())
+}
+
+#[test]
+fn lint_package() -> Result<()> {
+    let pkg = Package::snippets(
+        "lint",
+        "Lint",
+        "The lint test",
+        "1.0.0".parse()?,
+        "images/icon.png",
+        None::<&str>,
+        vec![
+            Snippets::new("rust", "Operators", "The operator snippets", vec![
+                Snippet::operator("operator-break", "break", Some("")),
+                Snippet::operator("operator-break", "break", None),
+                Snippet::operator("operator-continue", "continue", None),
+            ]),
+            Snippets::new("rust,toml", "Text", "The text snippets", vec![
+                Snippet::text("hello", "hi", "Hello"),
+                Snippet::text("hello-toml", "hi", "Hello").set_lang("toml"),
+                Snippet::text("continue", "continue", "continue;"),
+                Snippet::new("home", "The home directory", "home", vec!["${HOME_DIR:~}"]),
+            ]),
+            // the scope is ignored in the language snippets file:
+            Snippets::new("rust", "Greetings", "The greeting snippets", vec![
+                Snippet::text("greeting", "hi", "Hello!").set_lang("toml"),
+            ]).set_file_name("rust.json"),
+            // the snippets without language are shown everywhere:
+            Snippets::new("", "Global", "The global snippets", vec![
+                Snippet::text("todo", "todo", "TODO: $0"),
+                Snippet::text("todo-2", "todo", "TODO($1): $0"),
+                Snippet::text("hi", "hi-all", "Hi all"),
+            ]),
+        ],
+        License::mit("DrakeN-inc"),
+    );
+
+    let lints = pkg.lint().iter().map(|l| l.to_string()).collect::<Vec<_>>();
+    assert_eq!(lints, [
+        "[rust] duplicate name 'operator-break': Operators/operator-break, Operators/operator-break-2",
+        "[rust] duplicate prefix 'continue': Operators/operator-continue, Text/continue",
+        "[rust] duplicate prefix 'hi': Text/hello, Greetings/greeting",
+        "[rust] prefix 'break' shadows the longer prefix: Operators/operator-break-2, Operators/operator-break",
+        "[rust] prefix 'hi' shadows the longer prefix: Text/hello, Global/hi",
+        "[rust] prefix 'hi' shadows the longer prefix: Greetings/greeting, Global/hi",
+        "[rust] identical body: Operators/operator-continue, Text/continue",
+        "[toml] duplicate prefix 'hi': Text/hello, Text/hello-toml",
+        "[toml] prefix 'hi' shadows the longer prefix: Text/hello, Global/hi",
+        "[toml] prefix 'hi' shadows the longer prefix: Text/hello-toml, Global/hi",
+        "[toml] identical body: Text/hello, Text/hello-toml",
+        "[*] duplicate prefix 'todo': Global/todo, Global/todo-2",
+        "[rust,toml] unknown variable 'HOME_DIR': Text/home",
+    ]);
+    assert_eq!(pkg.lint()[0].kind, LintKind::DuplicateName);
+
+    // the lints are reported by validation as warnings:
+    let diags = pkg.validate(env!("CARGO_TARGET_TMPDIR"));
+    let lint = diags.iter().find(|d| d.kind == DiagnosticKind::SnippetLint(LintKind::DuplicateName)).unwrap();
+    assert!(!lint.is_error());
+    assert_eq!(lint.to_string(), "warning: [rust] duplicate name 'operator-break': Operators/operator-break, Operators/operator-break-2");
+    assert_eq!(diags.iter().filter(|d| matches!(d.kind, DiagnosticKind::SnippetLint(_))).count(), lints.len());
+
+    Ok(())
+}
+
+#[test]
+fn manifest_fields() -> Result<()> {
+    // the unset fields aren't written:
+    let json: serde_json::Value = serde_json::from_str(&snippets_package().to_json()?)?;
+    assert_eq!(json["license"], "MIT");
+    for field in ["publisher", "keywords", "author", "homepage", "bugs", "galleryBanner", "preview", "pricing", "badges", "qna", "sponsor"] {
+        assert!(json.get(field).is_none(), "{field}");
+    }
+
+    let pkg = snippets_package()
+        .set_publisher("DrakeN-inc")
+        .set_keywords(vec!["rust", "snippets"])
+        .set_author(Person::from("Drake N <drake@example.com> (https://example.com)"))
+        .set_homepage("https://example.com/rust-snippets")
+        .set_bugs("https://github.com/DrakeN-inc/vscode-rust-snippets/issues")
+        .set_gallery_banner(GalleryBanner::new("#C80000", BannerTheme::Dark))
+        .set_preview(true)
+        .set_pricing(Pricing::Free)
+        .add_badge(Badge::new("https://img.shields.io/badge/rust-1.80-orange", "https://rust-lang.org", "Rust"))
+        .set_qna(Qna::Disabled)
+        .set_sponsor("https://github.com/sponsors/DrakeN-inc");
+
+    let json: serde_json::Value = serde_json::from_str(&pkg.to_json()?)?;
+    assert_eq!(json["publisher"], "DrakeN-inc");
+    assert_eq!(json["keywords"], serde_json::json!(["rust", "snippets"]));
+    assert_eq!(json["author"], serde_json::json!({ "name": "Drake N", "email": "drake@example.com", "url": "https://example.com" }));
+    assert_eq!(json["bugs"]["url"], "https://github.com/DrakeN-inc/vscode-rust-snippets/issues");
+    assert_eq!(json["galleryBanner"], serde_json::json!({ "color": "#C80000", "theme": "dark" }));
+    assert_eq!((&json["preview"], &json["pricing"], &json["qna"]), (&true.into(), &"Free".into(), &false.into()));
+    assert_eq!(json["badges"][0]["description"], "Rust");
+    assert_eq!(json["sponsor"]["url"], "https://github.com/sponsors/DrakeN-inc");
+
+    // the fields are loaded back:
+    let dir = temp_dir("manifest_fields");
+    pkg.write_to(&dir)?;
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.publisher(), Some("DrakeN-inc"));
+    assert_eq!(loaded.author().unwrap().email.as_deref(), Some("drake@example.com"));
+    assert_eq!(loaded.bugs(), Some(&Bugs::from("https://github.com/DrakeN-inc/vscode-rust-snippets/issues")));
+    assert_eq!(loaded.qna(), Some(&Qna::Disabled));
+    assert_eq!(loaded.to_json()?, pkg.to_json()?);
+
+    Ok(())
+}
+
+#[test]
+fn package_categories() -> Result<()> {
+    let pkg = snippets_package().add_category(Category::ProgrammingLanguages).add_category(Category::Snippets);
+    assert_eq!(pkg.categories(), [Category::Snippets, Category::ProgrammingLanguages]);
+
+    let json: serde_json::Value = serde_json::from_str(&pkg.to_json()?)?;
+    assert_eq!(json["categories"], serde_json::json!(["Snippets", "Programming Languages"]));
+
+    let categories: Vec<Category> = serde_json::from_str(r#"["SCM Providers", "Machine Learning", "AI", "Unknown"]"#)?;
+    assert_eq!(categories, [Category::ScmProviders, Category::MachineLearning, Category::AI, Category::Other]);
+    assert_eq!(Category::LanguagePacks.to_string(), "Language Packs");
+
+    Ok(())
+}
+
+#[test]
+fn validate_package() -> Result<()> {
+    let dir = temp_dir("validate_package");
+    let pkg = snippets_package();
+    pkg.write_to(&dir)?;
+
+    let kinds = |pkg: &Package| pkg.validate(&dir).iter().map(|d| d.kind).collect::<Vec<_>>();
+    assert_eq!(kinds(&pkg), [DiagnosticKind::InvalidId, DiagnosticKind::MissingPublisher, DiagnosticKind::MissingIcon]);
+
+    let diags = pkg.validate(&dir);
+    assert_eq!(diags[0].severity(), Severity::Error);
+    assert_eq!(diags[0].to_string(), "error: the package id 'vscode_rust_snippets' must be lowercase && contain only 'a-z', '0-9', '-'");
+
+    // the icon is created, but the contributed file is removed:
+    std::fs::create_dir_all(dir.join("images"))?;
+    std::fs::write(dir.join("images/icon.png"), [0u8; 8])?;
+    std::fs::remove_file(dir.join("snippets/operators.code-snippets"))?;
+    assert_eq!(kinds(&pkg.clone().set_publisher("DrakeN inc")), [
+        DiagnosticKind::InvalidId, DiagnosticKind::InvalidPublisher, DiagnosticKind::MissingContribution,
+    ]);
+
+    // the package without repository && with relative README image:
+    let pkg = Package::snippets(
+        "rust-snippets", "Rust snippets", "", "1.0.0-beta".parse()?, "images/icon.svg", None::<&str>, vec![], License::mit("DrakeN-inc"),
+    ).set_publisher("DrakeN-inc");
+    std::fs::write(dir.join("README.md"), "# Rust\n![demo](images/demo.gif)\n<img src=\"https://example.com/logo.png\">\n![old](http://example.com/old.png)")?;
+    std::fs::remove_file(dir.join("LICENSE.md"))?;
+
+    let diags = pkg.validate(&dir);
+    assert_eq!(diags.iter().map(|d| d.kind).collect::<Vec<_>>(), [
+        DiagnosticKind::PreReleaseVersion, DiagnosticKind::EmptyDescription, DiagnosticKind::SvgIcon,
+        DiagnosticKind::MissingIcon, DiagnosticKind::RelativeReadmeImage, DiagnosticKind::InsecureReadmeImage, DiagnosticKind::MissingLicense,
+    ]);
+    assert_eq!(diags[4].value, "images/demo.gif");
+    assert_eq!(diags[5].to_string(), "error: the README image 'http://example.com/old.png' must come from HTTPS source");
+    assert_eq!(diags.iter().filter(|d| d.is_error()).count(), 5);
+
+    // the HTTP image is rejected even if the repository is set:
+    let pkg = Package::snippets(
+        "rust-snippets", "Rust snippets", "", "1.0.0".parse()?, "images/icon.png", Some("https://github.com/DrakeN-inc/rust-snippets"), vec![], License::mit("DrakeN-inc"),
+    ).set_publisher("DrakeN-inc");
+    assert!(pkg.validate(&dir).iter().any(|d| d.kind == DiagnosticKind::InsecureReadmeImage));
+    assert!(!pkg.validate(&dir).iter().any(|d| d.kind == DiagnosticKind::RelativeReadmeImage));
+
+    Ok(())
+}
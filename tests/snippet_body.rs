@@ -0,0 +1,67 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Snippet, SnippetBody };
+
+#[test]
+fn build_body() -> Result<()> {
+    let body = SnippetBody::new()
+        .text("let price = \"$\\\"; // \\$").newline()
+        .placeholder(1, "${name}").text(" ")
+        .choice(2, &["Eq, PartialEq", "a|b", "c\\d"]).text(" ")
+        .variable("TM_FILENAME", Some("main.rs")).newline()
+        .final_cursor();
+
+    assert_eq!(body.lines(), &[
+        r#"let price = "\$\"; // \\\$"#.to_owned(),
+        r#"${1:\${name\}} ${2|Eq\, PartialEq,a\|b,c\\d|} ${TM_FILENAME:main.rs}"#.to_owned(),
+        "$0".to_owned(),
+    ]);
+
+    let snippet = Snippet::new("test", "test", "test", body);
+    snippet.validate()?;
+
+    Ok(())
+}
+
+#[test]
+fn helpers_are_well_formed() -> Result<()> {
+    let attr = Snippet::attribute("attr-derive", "derive", Some(vec!["Debug", "Eq, PartialEq"]));
+    assert_eq!(attr.body, vec![r#"#[derive(${1|Debug,Eq\, PartialEq|})]"#]);
+
+    let attr = Snippet::attribute("attr-cfg", "cfg", Some(vec![""]));
+    assert_eq!(attr.body, vec!["#[cfg($1)]"]);
+
+    let func = Snippet::function("fn-format", "format!", None, Some("\"${}\""));
+    assert_eq!(func.body, vec![r#"format!(${1:"\${\}"})"#]);
+
+    for snippet in [attr, func, Snippet::double_block("block-if-else", "if", "else"), Snippet::operator("op-ret", "return", Some("$"))] {
+        snippet.validate()?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn comment_snippets() -> Result<()> {
+    let check = |snippet: Snippet, descr: &str, body: &str| {
+        snippet.validate().unwrap();
+        assert_eq!((snippet.description.as_str(), snippet.body.join("\n").as_str()), (descr, body));
+    };
+
+    check(Snippet::comment("todo", "TODO"), "TODO: ...", "${LINE_COMMENT} TODO: ${1:...}");
+    check(Snippet::block_comment("fixme", "FIXME"), "FIXME: ...", "${BLOCK_COMMENT_START} FIXME: ${1:...} ${BLOCK_COMMENT_END}");
+
+    check(Snippet::comment_for("todo", "rust", "TODO"), "// TODO: ...", "// TODO: ${1:...}");
+    check(Snippet::comment_for("todo", "python", "TODO"), "# TODO: ...", "# TODO: ${1:...}");
+    check(Snippet::comment_for("todo", "html", "TODO"), "<!-- TODO: ... -->", "<!-- TODO: ${1:...} -->");
+    check(Snippet::comment_for("todo", "unknown-lang", "TODO"), "TODO: ...", "${LINE_COMMENT} TODO: ${1:...}");
+
+    check(Snippet::block_comment_for("fixme", "lua", "FIXME"), "--[[ FIXME: ... ]]", "--[[ FIXME: ${1:...} ]]");
+    check(Snippet::block_comment_for("fixme", "shellscript", "FIXME"), "# FIXME: ...", "# FIXME: ${1:...}");
+    check(
+        Snippet::block_comment_for("fixme", "unknown-lang", "FIXME"),
+        "FIXME: ...",
+        "${BLOCK_COMMENT_START} FIXME: ${1:...} ${BLOCK_COMMENT_END}",
+    );
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Snippets, Snippet, SnippetsOrder, SnippetsDoc };
+
+#[test]
+fn snippet_keys() {
+    let mut snips = Snippets::new("rust", "Operators", "The operator snippets", vec![
+        Snippet::operator("operator-break", "break", Some("")),
+        Snippet::operator("operator-break", "break", None),
+    ]);
+    assert_eq!(snips.add_snippet(Snippet::operator("operator break", "break", None)), "operator-break-3");
+
+    assert_eq!(snips.get("operator-break").unwrap().prefix(), "break ");
+    assert_eq!(snips.get("operator-break-2").unwrap().prefix(), "break");
+    assert_eq!(snips.renamed_keys(), &[
+        ("operator-break".to_owned(), "operator-break-2".to_owned()),
+        ("operator-break".to_owned(), "operator-break-3".to_owned()),
+    ]);
+}
+
+#[test]
+fn snippets_order() -> Result<()> {
+    let snips = || Snippets::new("rust", "Blocks", "The block snippets", vec![
+        Snippet::block("block-while", "while"),
+        Snippet::block("block-if", "if"),
+        Snippet::simple_block("block-loop", "loop"),
+    ]);
+    let keys = |json: String| -> Vec<String> {
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let mut keys = value.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+        keys.sort_by_key(|k| json.find(&format!("\"{k}\"")).unwrap());
+        keys
+    };
+
+    assert_eq!(keys(snips().to_json()?), ["block-while", "block-if", "block-loop"]);
+    assert_eq!(snips().to_json()?, snips().to_json()?);
+
+    let by_prefix = snips().set_order(SnippetsOrder::Prefix);
+    assert_eq!(keys(by_prefix.to_json()?), ["block-if", "block-loop", "block-while"]);
+    let doc = by_prefix.documentation();
+    assert_eq!(doc.body.iter().map(|(p, _)| p.as_str()).collect::<Vec<_>>(), ["if {}", "loop {}", "while {}"]);
+
+    let custom = snips().set_order(SnippetsOrder::Custom(|s| format!("{:03}", 100 - s.prefix().len())));
+    assert_eq!(keys(custom.to_json()?), ["block-while", "block-loop", "block-if"]);
+
+    Ok(())
+}
+
+#[test]
+fn import_snippets() -> Result<()> {
+    let json = r#"{
+        // the line comment
+        "Print to console": {
+            "scope": "javascript,typescript",
+            "prefix": ["log", "console"],
+            "body": ["console.log('$1');", "$2"],
+            "description": "Log output to console", /* the block comment */
+        },
+        "for-loop": {
+            "prefix": "for",
+            "body": "for (const ${2:element} of ${1:array}) {\n\t$0\n}",
+        },
+        "broken": { "prefix": "br", "body": ["${1:oops"] },
+        "no-body": { "prefix": "nb" },
+    }"#;
+
+    let (snips, skipped) = Snippets::from_json("javascript", "Imported", "", json)?;
+
+    let keys = snips.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>();
+    assert_eq!(keys, ["Print to console", "for-loop"]);
+
+    let log = snips.get("Print to console").unwrap();
+    assert_eq!(log.prefixes, ["log", "console"]);
+    assert_eq!(log.languages, ["javascript", "typescript"]);
+    assert_eq!(log.description, "Log output to console");
+    assert_eq!(snips.get("for-loop").unwrap().body, ["for (const ${2:element} of ${1:array}) {", "\t$0", "}"]);
+
+    let skipped = skipped.iter().map(|s| (s.key.as_str(), s.reason.as_str())).collect::<Vec<_>>();
+    assert_eq!(skipped, [
+        ("broken", "invalid body: line 1, column 1: unclosed '${'"),
+        ("no-body", "the 'body' field is missing"),
+    ]);
+
+    // the keys && scopes are written back as is:
+    let json: serde_json::Value = serde_json::from_str(&snips.to_json()?)?;
+    assert_eq!(json.as_object().unwrap().keys().collect::<Vec<_>>(), ["Print to console", "for-loop"]);
+    assert_eq!(json["Print to console"]["scope"], "javascript,typescript");
+    assert!(json["for-loop"].get("scope").is_none());
+
+    let err = Snippet::from_json_value("no-body", &serde_json::json!({ "prefix": "nb" })).unwrap_err();
+    assert!(matches!(&err, Error::InvalidSnippetJson { name, .. } if name == "no-body"), "{err}");
+
+    Ok(())
+}
+
+#[test]
+fn multiple_prefixes() -> Result<()> {
+    let snips = Snippets::new("rust", "Blocks", "The block snippets", vec![
+        Snippet::block("block-for", "for").add_prefix("for in").add_prefix("foreach"),
+        Snippet::simple_block("block-loop", "loop"),
+    ]);
+
+    let json: serde_json::Value = serde_json::from_str(&snips.to_json()?).unwrap();
+    assert_eq!(json["block-for"]["prefix"], serde_json::json!(["for {}", "for in", "foreach"]));
+    assert_eq!(json["block-loop"]["prefix"], serde_json::json!("loop {}"));
+
+    let doc = snips.documentation().to_string();
+    assert!(doc.contains("| for {}, for in, foreach   | for ... { ... }   |"), "{doc}");
+
+    // the imported prefixes array is kept:
+    let (imported, _) = Snippets::from_json("rust", "Blocks", "", &snips.to_json()?)?;
+    assert_eq!(imported.get("block-for").unwrap().prefixes, ["for {}", "for in", "foreach"]);
+
+    Ok(())
+}
+
+#[test]
+fn snippets_doc_escape() {
+    let mut doc = SnippetsDoc::new("rust", "Closures", "The closure snippets");
+    doc.write_line("|x|", "|x| { ... }");
+    assert!(doc.to_string().ends_with("| \\|x\\|   | \\|x\\| { ... }   |\n"));
+}
@@ -0,0 +1,90 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Snippet, SnippetNode, SyntaxErrorKind, SnippetVariable, FormatItem, CaseModifier };
+
+#[test]
+fn parse_body() -> Result<()> {
+    let snippet = Snippet::new("test", "test", "test", vec![
+        "for (${1:k, v}) in ${2|iter(),iter_mut()|} {",
+        "    ${TM_FILENAME/(.*)\\..+$/${1:/upcase}/} \\$0",
+        "}$0",
+    ]);
+
+    let nodes = snippet.syntax_tree()?;
+    assert_eq!(nodes[1], SnippetNode::Placeholder { index: 1, value: vec![SnippetNode::Text("k, v".into())] });
+    assert_eq!(nodes[3], SnippetNode::Choice { index: 2, options: vec!["iter()".into(), "iter_mut()".into()] });
+
+    let SnippetNode::Variable { name, transform: Some(transform), .. } = &nodes[5] else { panic!("expected variable") };
+    assert_eq!(*name, SnippetVariable::TmFilename);
+    assert_eq!(transform.regex, "(.*)\\..+$");
+    assert_eq!(transform.format, vec![FormatItem::Case(1, CaseModifier::Upcase)]);
+
+    assert_eq!(nodes[6], SnippetNode::Text(" $0\n}".into()));
+    assert_eq!(nodes[7], SnippetNode::Tabstop { index: 0, transform: None });
+
+    Ok(())
+}
+
+#[test]
+fn literal_backslashes() {
+    // the backslash which escapes nothing is a plain text (as in VS Code):
+    assert_eq!(SnippetNode::parse("path C:\\").unwrap(), vec![SnippetNode::Text("path C:\\".into())]);
+    assert_eq!(SnippetNode::parse("\\d+ \\$1").unwrap(), vec![SnippetNode::Text("\\d+ $1".into())]);
+    assert_eq!(
+        SnippetNode::parse("${1|a\\b,c\\,d,e\\$|}").unwrap(),
+        vec![SnippetNode::Choice { index: 1, options: vec!["a\\b".into(), "c,d".into(), "e\\$".into()] }]
+    );
+
+    assert!(Snippet::new("path", "", "", vec!["let dir = \"C:\\\\${1:dir}\\\\\";"]).validate().is_ok());
+}
+
+#[test]
+fn literal_braces() {
+    // the '${' which starts no tabstop or variable is a plain text (as in VS Code):
+    assert_eq!(SnippetNode::parse("echo ${#arr[@]}").unwrap(), vec![SnippetNode::Text("echo ${#arr[@]}".into())]);
+    assert_eq!(SnippetNode::parse("${1 }${}").unwrap(), vec![SnippetNode::Text("${1 }${}".into())]);
+    assert_eq!(SnippetNode::parse("mv $1 ${var%%.*}.$2").unwrap(), vec![
+        SnippetNode::Text("mv ".into()),
+        SnippetNode::Tabstop { index: 1, transform: None },
+        SnippetNode::Text(" ${var%%.*}.".into()),
+        SnippetNode::Tabstop { index: 2, transform: None },
+    ]);
+    assert_eq!(SnippetNode::parse("${#a[$1]}").unwrap()[1], SnippetNode::Tabstop { index: 1, transform: None });
+
+    assert!(Snippet::new("count", "", "", vec!["echo ${#${1:arr}[@]}"]).validate().is_ok());
+}
+
+#[test]
+fn unknown_variables() -> Result<()> {
+    // the unknown variables are parsed (VS Code inserts their names), but reported:
+    let snippet = Snippet::new("test", "", "", vec!["$TM_FILE_NAME ${CURRENT_DAY:1} ${1:${TM_FOO/(.*)/$1/}} $TM_FILENAME"]);
+    let nodes = snippet.syntax_tree()?;
+    assert_eq!(nodes[0], SnippetNode::UnknownVariable { name: "TM_FILE_NAME".into(), default: None, transform: None });
+    assert_eq!(nodes[2], SnippetNode::UnknownVariable { name: "CURRENT_DAY".into(), default: Some(vec![SnippetNode::Text("1".into())]), transform: None });
+    assert_eq!(snippet.unknown_variables()?, ["TM_FILE_NAME", "CURRENT_DAY", "TM_FOO"]);
+    snippet.validate()?;
+
+    let exp = Snippet::new("test", "", "", vec!["$TM_FOO ${BAR:bar} ${BAZ/(.*)/x$1/}"]).expand(&vscode_generator::ExpansionContext::new())?;
+    assert_eq!(exp.text, "TM_FOO bar x");
+
+    Ok(())
+}
+
+#[test]
+fn syntax_errors() {
+    let check = |body: Vec<&str>, line, column, kind| {
+        let err = SnippetNode::parse(&body.join("\n")).unwrap_err();
+        assert_eq!((err.line, err.column, err.kind), (line, column, kind), "{body:?}");
+    };
+
+    check(vec!["let x = 1;", "    ${1:foo", "x"], 2, 5, SyntaxErrorKind::UnclosedBrace);
+    check(vec!["#[derive(${1|a,b})]"], 1, 10, SyntaxErrorKind::MalformedChoice("the choice list is not closed by '|}'"));
+    check(vec!["${1|a,,b|}"], 1, 7, SyntaxErrorKind::MalformedChoice("empty choice option"));
+    check(vec!["${1|a\\"], 1, 1, SyntaxErrorKind::MalformedChoice("the choice list is not closed by '|}'"));
+    check(vec!["${1/.*/$0/x}"], 1, 11, SyntaxErrorKind::UnexpectedChar('x'));
+
+    let err = SnippetNode::parse("mod ${TM_FILENAME/(.*/$1/};").unwrap_err();
+    assert!(matches!(err.kind, SyntaxErrorKind::InvalidRegex(_)) && err.column == 5, "{err}");
+
+    let err = Snippet::new("broken", "", "", vec!["${1:foo"]).validate().unwrap_err();
+    assert_eq!(err.to_string(), "Invalid snippet 'broken' body: line 1, column 1: unclosed '${'");
+}
@@ -0,0 +1,107 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Package, ColorTheme, ThemeKind, TokenColor, SemanticTokenStyle, Category };
+use std::path::PathBuf;
+
+mod common;
+use common::{ temp_dir, empty_package };
+
+fn sample_theme() -> ColorTheme {
+    ColorTheme::new("Company Dark", ThemeKind::Dark)
+        .set_color("editor.background", "#1E1E1E")
+        .set_color("editor.foreground", "#D4D4D4")
+        .add_token_color(TokenColor::new("comment").foreground("#6A9955").font_style("italic"))
+        .add_token_color(TokenColor::new("string, string.quoted").set_name("Strings").foreground("#CE9178"))
+        .set_semantic_color("variable.readonly", SemanticTokenStyle::color("#4FC1FF"))
+        .set_semantic_color("function", SemanticTokenStyle::color("#DCDCAA").bold(true))
+}
+
+fn sample_package() -> Package {
+    let mut pkg = empty_package("company-themes", "Company themes", "The company color themes");
+    pkg.add_theme(sample_theme());
+    pkg
+}
+
+#[test]
+fn theme_json() -> Result<()> {
+    let theme = sample_theme();
+    assert_eq!(theme.file_name, PathBuf::from("company-dark-color-theme.json"));
+    assert_eq!(theme.color("editor.background"), Some("#1E1E1E"));
+
+    let json: serde_json::Value = serde_json::from_str(&theme.to_json()?)?;
+    assert_eq!(json["type"], "dark");
+    assert_eq!(json["colors"]["editor.foreground"], "#D4D4D4");
+    assert_eq!(json["tokenColors"][0], serde_json::json!({ "scope": "comment", "settings": { "foreground": "#6A9955", "fontStyle": "italic" } }));
+    assert_eq!(json["tokenColors"][1]["scope"], serde_json::json!(["string", "string.quoted"]));
+    assert_eq!(json["semanticTokenColors"]["variable.readonly"], "#4FC1FF");
+    assert_eq!(json["semanticTokenColors"]["function"], serde_json::json!({ "foreground": "#DCDCAA", "bold": true }));
+
+    // the round trip:
+    assert_eq!(ColorTheme::from_json(&theme.to_json()?)?, theme);
+
+    // the hand written theme:
+    let theme = ColorTheme::from_json(r##"{
+        // comment
+        "name": "Light+", "type": "hcLight",
+        "tokenColors": [{ "scope": "keyword, storage", "settings": { "foreground": "#00F" } }],
+    }"##)?;
+    assert_eq!(theme.kind, ThemeKind::HighContrastLight);
+    assert_eq!(theme.kind.ui_theme(), "hc-light");
+    assert_eq!(theme.token_colors()[0].scope, ["keyword", "storage"]);
+
+    Ok(())
+}
+
+#[test]
+fn validate_theme() {
+    assert!(sample_theme().validate().is_ok());
+
+    let err = sample_theme().set_color("editor.background", "red").validate().unwrap_err();
+    assert_eq!(err.to_string(), "Invalid color theme 'Company Dark': the editor.background color 'red' isn't hex color");
+
+    let err = sample_theme().add_token_color(TokenColor::new("keyword").font_style("bold oblique")).validate().unwrap_err();
+    assert!(err.to_string().contains("font style 'oblique'"));
+
+    assert!(sample_theme().set_semantic_color("type", SemanticTokenStyle::color("#12345")).validate().is_err());
+    assert!(ColorTheme::is_color("#ABCD") && ColorTheme::is_color("#aabbccdd") && !ColorTheme::is_color("#GGG"));
+}
+
+#[test]
+fn theme_package() -> Result<()> {
+    let pkg = sample_package();
+    assert_eq!(pkg.categories(), [Category::Themes]);
+
+    let json: serde_json::Value = serde_json::from_str(&pkg.to_json()?)?;
+    assert_eq!(json["contributes"], serde_json::json!({
+        "themes": [{ "label": "Company Dark", "uiTheme": "vs-dark", "path": "themes/company-dark-color-theme.json" }]
+    }));
+
+    let files = pkg.render()?;
+    assert!(files.contains_key(&PathBuf::from("themes/company-dark-color-theme.json")));
+    let readme = String::from_utf8_lossy(&files[&PathBuf::from("README.md")]).to_string();
+    assert!(readme.contains("# Company Dark [Dark theme]:"));
+    assert!(readme.contains("| `editor.background` | `#1E1E1E` |"));
+    assert!(readme.contains("| comment               | `#6A9955 italic` |"));
+
+    // the invalid theme isn't written:
+    let mut pkg = sample_package();
+    pkg.add_theme(ColorTheme::new("Broken", ThemeKind::Light).set_color("editor.background", "#12"));
+    assert!(matches!(pkg.render(), Err(Error::InvalidTheme { .. })));
+
+    // loading the theme package:
+    let dir = temp_dir("theme_package");
+    sample_package().write_to(&dir)?;
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.themes(), [sample_theme()]);
+    assert_eq!(loaded.contributes().themes.len(), 1);
+
+    // the theme file without name && type takes them from the contribution:
+    let manifest = std::fs::read_to_string(dir.join("package.json"))?.replace("\"vs-dark\"", "\"vs\"");
+    std::fs::write(dir.join("package.json"), manifest)?;
+    std::fs::write(dir.join("themes/company-dark-color-theme.json"), r##"{ "colors": { "editor.background": "#FFFFFF" } }"##)?;
+    let loaded = Package::load(&dir)?.0;
+    assert_eq!(loaded.themes()[0].name, "Company Dark");
+    assert_eq!(loaded.themes()[0].kind, ThemeKind::Light);
+    assert_eq!(loaded.themes()[0].color("editor.background"), Some("#FFFFFF"));
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Version, VersionReq, VersionOp };
+
+#[test]
+fn parse_versions() -> Result<()> {
+    let ver: Version = "1.2.3-beta.1+build.5".parse()?;
+    assert_eq!((ver.major, ver.minor, ver.patch), (1, 2, 3));
+    assert_eq!((ver.pre.as_str(), ver.build.as_str()), ("beta.1", "build.5"));
+    assert_eq!(ver.to_string(), "1.2.3-beta.1+build.5");
+
+    let reason = |s: &str| s.parse::<Version>().unwrap_err().reason;
+    assert_eq!(reason("1"), "expected the format 'MAJOR.MINOR.PATCH'");
+    assert_eq!(reason("1.2.3.4"), "expected the format 'MAJOR.MINOR.PATCH'");
+    assert_eq!(reason("^1.2.3"), "the version number '^1' isn't a number");
+    assert_eq!(reason("1.02.3"), "the version number '02' has leading zeros");
+    assert_eq!(reason("1.2.3-"), "the pre-release is empty");
+    assert_eq!(reason("1.2.3-rc..1"), "the empty pre-release or build identifier");
+    assert_eq!(reason("1.2.3-rc_1"), "the identifier 'rc_1' has invalid symbols");
+
+    let err = Error::from("1.x.0".parse::<Version>().unwrap_err());
+    assert_eq!(err.to_string(), "Invalid package version: incorrect version '1.x.0': the version number 'x' isn't a number");
+
+    Ok(())
+}
+
+#[test]
+fn compare_and_bump() -> Result<()> {
+    let mut versions = ["1.0.0", "1.0.0-rc.1", "1.0.0-alpha.beta", "1.0.0-beta.11", "0.9.12", "1.0.0-alpha", "1.0.0-beta.2", "1.0.0-alpha.1"]
+        .iter()
+        .map(|s| s.parse())
+        .collect::<std::result::Result<Vec<Version>, _>>()?;
+    versions.sort();
+
+    let sorted = versions.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    assert_eq!(sorted, ["0.9.12", "1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-alpha.beta", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0"]);
+
+    let ver = Version::new(1, 2, 3);
+    assert_eq!(ver.bump_major(), Version::new(2, 0, 0));
+    assert_eq!(ver.bump_minor(), Version::new(1, 3, 0));
+    assert_eq!(ver.bump_patch(), Version::new(1, 2, 4));
+    assert_eq!(Version::new(2, 0, 0).set_pre("rc.1")?.bump_major(), Version::new(2, 0, 0));
+    assert!(Version::new(1, 2, 3).set_build("01")? > ver);
+
+    Ok(())
+}
+
+#[test]
+fn version_requirements() -> Result<()> {
+    let matches = |req: &str, ver: &str| req.parse::<VersionReq>().unwrap().matches(&ver.parse().unwrap());
+
+    assert!(matches("^1.90.0", "1.95.2") && !matches("^1.90.0", "2.0.0") && !matches("^1.90.0", "1.89.9"));
+    assert!(matches("^0.3.1", "0.3.9") && !matches("^0.3.1", "0.4.0"));
+    assert!(matches("~1.2.3", "1.2.9") && !matches("~1.2.3", "1.3.0"));
+    assert!(matches(">=1.80.0", "2.1.0") && !matches("<1.80.0", "1.80.0"));
+    assert!(matches("1.2.3", "1.2.3") && matches("*", "0.0.1"));
+
+    let req: VersionReq = ">= 1.80.0".parse()?;
+    assert_eq!((req.op, req.to_string()), (VersionOp::GreaterEq, ">=1.80.0".to_owned()));
+    assert_eq!(
+        "^1.90".parse::<VersionReq>().unwrap_err().to_string(),
+        "incorrect version '^1.90': the range version is incorrect: expected the format 'MAJOR.MINOR.PATCH'"
+    );
+
+    Ok(())
+}
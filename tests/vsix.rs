@@ -0,0 +1,133 @@
+extern crate vscode_generator;
+use vscode_generator::{ prelude::*, Vsix, Package, GalleryBanner, BannerTheme };
+use std::{ io::Read, path::PathBuf };
+
+mod common;
+use common::{ empty_package, snippets_package };
+
+fn sample_package() -> Package {
+    snippets_package()
+        .set_descr("The snippets for Rust & friends")
+        .set_publisher("DrakeN-inc")
+        .set_keywords(vec!["rust"])
+        .set_gallery_banner(GalleryBanner::new("#C80000", BannerTheme::Dark))
+        .add_asset("images/icon.png", vec![0x89, b'P', b'N', b'G'])
+}
+
+#[test]
+fn write_vsix() -> Result<()> {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("write_vsix/vscode_rust_snippets-0.0.1.vsix");
+    let pkg = sample_package();
+    pkg.write_vsix(&path)?;
+
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(&path)?)?;
+    let mut names = zip.file_names().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, [
+        "[Content_Types].xml",
+        "extension.vsixmanifest",
+        "extension/LICENSE.md",
+        "extension/README.md",
+        "extension/images/icon.png",
+        "extension/package.json",
+        "extension/snippets/blocks.code-snippets",
+        "extension/snippets/operators.code-snippets",
+    ]);
+
+    let mut read = |name: &str| -> Result<String> {
+        let mut text = String::new();
+        zip.by_name(name)?.read_to_string(&mut text)?;
+        Ok(text)
+    };
+
+    let manifest = read("extension.vsixmanifest")?;
+    assert!(manifest.contains(r#"<Identity Language="en-US" Id="vscode_rust_snippets" Version="0.0.1" Publisher="DrakeN-inc" />"#));
+    assert!(manifest.contains("<Description xml:space=\"preserve\">The snippets for Rust &amp; friends</Description>"));
+    assert!(manifest.contains("<Tags>rust,snippet</Tags>"));
+    assert!(manifest.contains("<Categories>Snippets</Categories>"));
+    assert!(manifest.contains(r#"<Property Id="Microsoft.VisualStudio.Code.Engine" Value="^1.90.0" />"#));
+    assert!(manifest.contains(r#"<Property Id="Microsoft.VisualStudio.Services.Branding.Theme" Value="dark" />"#));
+    assert!(manifest.contains(r#"<Property Id="Microsoft.VisualStudio.Code.ExtensionKind" Value="ui,workspace,web" />"#));
+    assert!(manifest.contains("<Icon>extension/images/icon.png</Icon>"));
+    assert!(manifest.contains(r#"<Asset Type="Microsoft.VisualStudio.Services.Content.License" Path="extension/LICENSE.md" Addressable="true" />"#));
+
+    let types = read("[Content_Types].xml")?;
+    assert!(types.contains(r#"<Default Extension=".json" ContentType="application/json" />"#));
+    assert!(types.contains(r#"<Default Extension=".png" ContentType="image/png" />"#));
+
+    assert_eq!(read("extension/package.json")?, pkg.to_json()?);
+
+    Ok(())
+}
+
+#[test]
+fn vsix_extension_kind() -> Result<()> {
+    let kind = |pkg: Package| -> Result<String> {
+        let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("vsix_extension_kind.vsix");
+        pkg.write_vsix(&path)?;
+        Ok(Vsix::read(&path)?.manifest.property("Microsoft.VisualStudio.Code.ExtensionKind").unwrap_or_default().to_owned())
+    };
+
+    // the kinds are deduced like 'vsce' does:
+    assert_eq!(kind(sample_package())?, "ui,workspace,web");
+    assert_eq!(kind(sample_package().set_extra("main", "./out/extension.js"))?, "workspace");
+    assert_eq!(kind(sample_package().set_extra("main", "./out/extension.js").set_extra("browser", "./out/web.js"))?, "workspace,web");
+    assert_eq!(kind(sample_package().set_extra("browser", "./out/web.js"))?, "web");
+    assert_eq!(kind(sample_package().set_extra("extensionPack", serde_json::json!(["rust-lang.rust-analyzer"])))?, "workspace,web");
+    assert_eq!(kind(sample_package().set_extra("extensionKind", "ui"))?, "ui,workspace,web");
+    assert_eq!(kind(sample_package().set_extra("main", "./out/extension.js").set_extra("extensionKind", serde_json::json!(["workspace"])))?, "workspace");
+
+    Ok(())
+}
+
+#[test]
+fn vsix_requires_publisher() {
+    let pkg = empty_package("test", "Test", "Test");
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("vsix_requires_publisher.vsix");
+    assert!(matches!(pkg.write_vsix(&path), Err(Error::InvalidManifest(_))));
+}
+
+#[test]
+fn vsix_requires_icon() {
+    let pkg = empty_package("test", "Test", "Test")
+        .set_publisher("test");
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("vsix_requires_icon.vsix");
+
+    let err = pkg.write_vsix(&path).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid package manifest: the icon 'images/icon.png' is not added to package (see 'Package::add_asset')");
+    assert!(pkg.add_asset("images/icon.png", vec![0x89, b'P', b'N', b'G']).write_vsix(&path).is_ok());
+}
+
+#[test]
+fn read_vsix() -> Result<()> {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("read_vsix/vscode_rust_snippets-0.0.1.vsix");
+    let pkg = sample_package();
+    pkg.write_vsix(&path)?;
+
+    let vsix = Vsix::read(&path)?;
+    assert_eq!((vsix.manifest.id.as_str(), vsix.manifest.publisher.as_str()), ("vscode_rust_snippets", "DrakeN-inc"));
+    assert_eq!(vsix.manifest.description, "The snippets for Rust & friends");
+    assert_eq!(vsix.manifest.categories, ["Snippets"]);
+    assert_eq!(vsix.manifest.property("Microsoft.VisualStudio.Code.Engine"), Some("^1.90.0"));
+    assert!(vsix.readme.as_deref().unwrap().starts_with("# VSCode Rust snippets"));
+    assert!(vsix.skipped.is_empty());
+
+    let file = vsix.files.iter().find(|f| f.path == "extension/snippets/blocks.code-snippets").unwrap();
+    assert_eq!(file.size as usize, pkg.snippets_groups()[0].to_json()?.len());
+
+    // the package is restored with snippets, license && icon:
+    let loaded = &vsix.package;
+    assert_eq!(loaded.to_json()?, pkg.to_json()?);
+    assert_eq!(loaded.snippets_groups()[0].to_json()?, pkg.snippets_groups()[0].to_json()?);
+    assert_eq!(loaded.license().unwrap().as_str(), pkg.license().unwrap().as_str());
+    assert_eq!(loaded.assets(), pkg.assets());
+
+    // the regenerated archive has the same files:
+    let data = std::fs::read(&path)?;
+    let again = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("read_vsix/again.vsix");
+    loaded.write_vsix(&again)?;
+    let paths = |vsix: Vsix| vsix.files.into_iter().map(|f| f.path).collect::<Vec<_>>();
+    assert_eq!(paths(Vsix::read(&again)?), paths(Vsix::from_bytes(&data)?));
+
+    Ok(())
+}